@@ -0,0 +1,104 @@
+use {
+    serde_json::Value,
+    std::{
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Plain JSON ABI or Foundry/Hardhat artifact.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    /// Artifact `contractName`, or the file name.
+    pub name: String,
+    pub path: PathBuf,
+    pub abi: Value,
+    pub bytecode: Option<String>,
+}
+
+/// Bytecode which still contains library placeholders and cannot be deployed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnlinkedBytecode;
+
+impl Artifact {
+    /// Reads a plain JSON ABI or a Foundry/Hardhat artifact, returning `None`
+    /// for other JSON files.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        let stem = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .trim_end_matches(".json")
+            .to_string();
+
+        let (name, abi, bytecode) = match value {
+            Value::Array(_) => (stem, value, None),
+            Value::Object(mut object) => match object.remove("abi") {
+                Some(abi @ Value::Array(_)) => {
+                    let name = match object.get("contractName") {
+                        Some(Value::String(name)) => name.clone(),
+                        _ => stem,
+                    };
+                    let bytecode = match object.get("bytecode").or_else(|| object.get("bin")) {
+                        Some(Value::String(bytecode)) => Some(bytecode.clone()),
+                        Some(Value::Object(bytecode)) => bytecode
+                            .get("object")
+                            .and_then(|object| object.as_str())
+                            .map(String::from),
+                        _ => None,
+                    };
+                    (name, abi, bytecode)
+                }
+                _ => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            name,
+            path: path.to_path_buf(),
+            abi,
+            bytecode,
+        }))
+    }
+
+    /// `0x` prefixed bytecode, or `None` if the artifact has none.
+    pub fn linked_bytecode(&self) -> Result<Option<String>, UnlinkedBytecode> {
+        let Some(bytecode) = &self.bytecode else {
+            return Ok(None);
+        };
+        let bytecode = bytecode.trim_start_matches("0x");
+        if !bytecode.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(UnlinkedBytecode);
+        }
+
+        Ok((!bytecode.is_empty()).then(|| format!("0x{bytecode}")))
+    }
+}
+
+/// Collects the JSON files under `dir` in path order, skipping compiler inputs
+/// and Hardhat debug files.
+pub fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if path.is_dir() {
+            // Hardhat and Foundry compiler inputs, not contract artifacts
+            if file_name != "build-info" {
+                collect_files(&path, files)?;
+            }
+        } else if file_name.ends_with(".json") && !file_name.ends_with(".dbg.json") {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+//! Code generation shared by the `ethers-abirpc` macros and
+//! `ethers_abirpc::build::RegistryBuilder`.
+
+pub mod artifact;
+pub mod registry;
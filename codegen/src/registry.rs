@@ -0,0 +1,809 @@
+use {
+    proc_macro2::{Span, TokenStream},
+    quote::{format_ident, quote},
+    syn::{Attribute, Ident, Path, Visibility},
+};
+
+/// Transports with an `AbiProviderTrait` implementation, see `transport`.
+pub const TRANSPORTS: [&str; 6] = ["Ws", "Ipc", "Http", "Retry", "Batch", "Mock"];
+
+/// Code generated by `abirpc!(Name)`: `NameRegistry<M>` with an
+/// `AbiProviderTrait` implementation for every transport,
+/// `NameMultiChainRegistry<M>` and the helpers on `Name<M>`.
+pub fn abirpc(krate: &Path, abi: &Ident) -> TokenStream {
+    let name = abi.to_string();
+    let vis = syn::parse_quote!(pub);
+    let registry_ident = format_ident!("{}Registry", abi);
+    let multichain_ident = format_ident!("{}MultiChainRegistry", abi);
+
+    let mut tokens = registry(krate, &[], &vis, &registry_ident, abi, &name);
+    for transport_name in TRANSPORTS {
+        let transport_ident = Ident::new(transport_name, Span::call_site());
+        tokens.extend(transport(krate, &registry_ident, &transport_ident));
+    }
+    tokens.extend(multichain(krate, &vis, &multichain_ident, abi));
+    tokens.extend(contract(krate, abi, &name));
+
+    tokens
+}
+
+/// Registry wrapping `AbiRegistry<abi<M>>`, its pinned variant returned by
+/// `at_block` and `from_config`. `name` is the contract name recorded in
+/// snapshots and decoded transactions.
+pub fn registry(
+    krate: &Path,
+    attrs: &[Attribute],
+    vis: &Visibility,
+    registry: &Ident,
+    abi: &Ident,
+    name: &str,
+) -> TokenStream {
+    let pinned = format_ident!("{}Pinned", registry);
+
+    quote! {
+        #(#attrs)*
+        #[derive(Debug)]
+        #vis struct #registry<M>(#krate::registry::AbiRegistry<#abi<M>>)
+        where
+            M: ::ethers::prelude::Middleware;
+
+        impl<M> #registry<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            pub fn new(url: String, chain: #krate::chain::Chain) -> Self {
+                let registry = #krate::registry::AbiRegistry::<#abi<M>>::new(url, chain);
+                Self(registry)
+            }
+
+            pub fn mock() -> Self {
+                let registry = #krate::registry::AbiRegistry::<#abi<M>>::mock();
+                Self(registry)
+            }
+
+            pub fn with_config(self, config: #krate::registry::RegistryConfig) -> Self {
+                Self(self.0.with_config(config))
+            }
+
+            pub fn config(&self) -> #krate::registry::RegistryConfig {
+                self.0.config
+            }
+
+            pub fn metrics(&self) -> #krate::registry::RegistryMetrics {
+                self.0.metrics()
+            }
+
+            pub fn capabilities(&self) -> Option<#krate::capabilities::Capabilities> {
+                self.0.capabilities()
+            }
+
+            pub fn purge_expired(&self) -> usize {
+                self.0.purge_expired()
+            }
+
+            pub fn register(&self, provider: M, address: ::ethers::prelude::Address) -> #abi<M> {
+                self.0
+                    .get_or_insert_with(address, || #abi::new(address, provider.into()))
+            }
+
+            /// Registers `address` after checking that its code matches
+            /// `verification`. Already registered entries are returned as is.
+            pub async fn register_verified(
+                &self,
+                provider: M,
+                address: ::ethers::prelude::Address,
+                verification: &#krate::verify::Verification,
+            ) -> Result<#abi<M>, #krate::error::Error> {
+                if let Some(instance) = self.0.get_entry(address) {
+                    return Ok(instance);
+                }
+
+                let instance = #abi::new(address, provider.into());
+                #krate::verify::verify_contract(
+                    instance.client_ref(),
+                    address,
+                    instance.abi(),
+                    verification,
+                )
+                .await?;
+                self.0.add_entry(address, instance.clone());
+
+                Ok(instance)
+            }
+
+            /// Registers `address` and records the implementation if it is a proxy.
+            pub async fn register_proxy(
+                &self,
+                provider: M,
+                address: ::ethers::prelude::Address,
+            ) -> Result<(#abi<M>, Option<#krate::proxy::ProxyInfo>), #krate::error::Error> {
+                let proxy = #krate::proxy::detect_proxy(&provider, address).await?;
+                let instance = self.register(provider, address);
+                self.0.update_metadata(address, |metadata| {
+                    metadata.proxy = proxy.map(|proxy| proxy.kind);
+                    metadata.implementation = proxy.map(|proxy| proxy.implementation);
+                });
+
+                Ok((instance, proxy))
+            }
+
+            /// Re-reads the proxy implementation of a registered entry and returns
+            /// the change if it differs from the recorded implementation.
+            pub async fn check_implementation(
+                &self,
+                address: ::ethers::prelude::Address,
+            ) -> Result<Option<#krate::proxy::ImplementationChange>, #krate::error::Error> {
+                let instance = match self.0.get_entry(address) {
+                    Some(instance) => instance,
+                    None => return Ok(None),
+                };
+
+                let proxy = #krate::proxy::detect_proxy(instance.client_ref(), address).await?;
+                let previous = self.0.get_metadata(address).and_then(|m| m.implementation);
+                let current = proxy.map(|proxy| proxy.implementation);
+                if previous == current {
+                    return Ok(None);
+                }
+
+                self.0.update_metadata(address, |metadata| {
+                    metadata.proxy = proxy.map(|proxy| proxy.kind);
+                    metadata.implementation = current;
+                });
+
+                Ok(Some(#krate::proxy::ImplementationChange {
+                    address,
+                    previous,
+                    current,
+                }))
+            }
+
+            /// Checks every entry recorded as a proxy, see `check_implementation`.
+            pub async fn check_implementations(
+                &self,
+            ) -> Result<Vec<#krate::proxy::ImplementationChange>, #krate::error::Error> {
+                let mut changes = vec![];
+                for address in self.0.addresses() {
+                    let metadata = self.0.get_metadata(address);
+                    if !metadata.is_some_and(|metadata| metadata.proxy.is_some()) {
+                        continue;
+                    }
+                    if let Some(change) = self.check_implementation(address).await? {
+                        changes.push(change);
+                    }
+                }
+
+                Ok(changes)
+            }
+
+            /// Deploys `bytecode` with `args` through `provider`, waits for the
+            /// receipt and registers the new instance.
+            pub async fn deploy<T>(
+                &self,
+                provider: M,
+                bytecode: ::ethers::prelude::Bytes,
+                args: T,
+            ) -> Result<#abi<M>, #krate::error::Error>
+            where
+                T: ::ethers::abi::Tokenize,
+            {
+                let client = std::sync::Arc::new(provider);
+                let abi = Self::contract_abi().clone();
+                let deployment =
+                    #krate::deploy::deploy(std::sync::Arc::clone(&client), abi, bytecode, args)
+                        .await?;
+
+                Ok(self.register_deployment(client, deployment))
+            }
+
+            /// Address at which `deploy_create2` deploys `bytecode` with `args`.
+            pub fn create2_address<T>(
+                &self,
+                bytecode: ::ethers::prelude::Bytes,
+                args: T,
+                salt: ::ethers::prelude::H256,
+            ) -> Result<::ethers::prelude::Address, #krate::error::Error>
+            where
+                T: ::ethers::abi::Tokenize,
+            {
+                let init_code =
+                    #krate::deploy::init_code(Self::contract_abi(), bytecode, args)?;
+
+                Ok(#krate::deploy::create2_address(&init_code, salt))
+            }
+
+            /// Deploys `bytecode` with `args` through the CREATE2 deployer and
+            /// registers the instance. Already deployed contracts are registered
+            /// without sending a transaction.
+            pub async fn deploy_create2<T>(
+                &self,
+                provider: M,
+                bytecode: ::ethers::prelude::Bytes,
+                args: T,
+                salt: ::ethers::prelude::H256,
+            ) -> Result<#abi<M>, #krate::error::Error>
+            where
+                T: ::ethers::abi::Tokenize,
+            {
+                let init_code =
+                    #krate::deploy::init_code(Self::contract_abi(), bytecode, args)?;
+                let deployment =
+                    #krate::deploy::deploy_create2(&provider, init_code, salt).await?;
+
+                Ok(self.register_deployment(std::sync::Arc::new(provider), deployment))
+            }
+
+            fn contract_abi() -> &'static ::ethers::abi::Abi {
+                Self::base_contract().abi()
+            }
+
+            /// Read from an instance rather than the abigen `<NAME>_ABI` static,
+            /// so that an unknown contract type is the only error reported.
+            fn base_contract() -> &'static std::sync::Arc<::ethers::contract::BaseContract> {
+                static BASE: std::sync::OnceLock<std::sync::Arc<::ethers::contract::BaseContract>> =
+                    std::sync::OnceLock::new();
+                BASE.get_or_init(|| {
+                    let (provider, _) = ::ethers::providers::Provider::mocked();
+                    let instance = #abi::new(
+                        ::ethers::prelude::Address::zero(),
+                        std::sync::Arc::new(provider),
+                    );
+                    std::sync::Arc::new(::ethers::contract::BaseContract::from(instance.abi().clone()))
+                })
+            }
+
+            fn register_deployment(
+                &self,
+                client: std::sync::Arc<M>,
+                deployment: #krate::deploy::Deployment,
+            ) -> #abi<M> {
+                let instance = #abi::new(deployment.address, client);
+                self.0.add_entry(deployment.address, instance.clone());
+                if let Some(block) = deployment.block_number() {
+                    self.0.update_metadata(deployment.address, |metadata| {
+                        metadata.deployment_block = Some(block);
+                    });
+                }
+
+                instance
+            }
+
+            pub fn register_as(
+                &self,
+                provider: M,
+                alias: impl Into<String>,
+                address: ::ethers::prelude::Address,
+            ) -> #abi<M> {
+                let instance = self.register(provider, address);
+                self.0.add_alias(alias, address);
+
+                instance
+            }
+
+            /// Registers every alias listed for this registry's chain in `book`.
+            pub fn register_address_book(
+                &self,
+                provider: M,
+                book: &#krate::address_book::AddressBook,
+            ) -> Result<Vec<(String, #abi<M>)>, #krate::error::Error>
+            where
+                M: Clone,
+            {
+                let chain = self.0.chain.ok_or(#krate::error::Error::MissingChain)?;
+                let addresses = book
+                    .addresses(chain)
+                    .ok_or(#krate::error::Error::UnknownChain { chain_id: chain.id() })?;
+
+                let instances = addresses
+                    .into_iter()
+                    .map(|(alias, address)| {
+                        let instance =
+                            self.register_as(provider.clone(), alias.clone(), address);
+                        (alias, instance)
+                    })
+                    .collect();
+
+                Ok(instances)
+            }
+
+            pub fn alias(
+                &self,
+                alias: impl Into<String>,
+                address: ::ethers::prelude::Address,
+            ) -> Option<::ethers::prelude::Address> {
+                self.0.add_alias(alias, address)
+            }
+
+            pub fn unalias(&self, alias: &str) -> Option<::ethers::prelude::Address> {
+                self.0.remove_alias(alias)
+            }
+
+            pub fn resolve(&self, alias: &str) -> Option<::ethers::prelude::Address> {
+                self.0.resolve_alias(alias)
+            }
+
+            pub fn get_by_alias(&self, alias: &str) -> Option<#abi<M>> {
+                self.0.get_entry_by_alias(alias)
+            }
+
+            pub fn aliases_of(&self, address: ::ethers::prelude::Address) -> Vec<String> {
+                self.0.aliases_of(address)
+            }
+
+            pub fn aliases(&self) -> Vec<(String, ::ethers::prelude::Address)> {
+                self.0.aliases()
+            }
+
+            pub fn unregister(&self, address: ::ethers::prelude::Address) -> Option<#abi<M>> {
+                self.0.remove_entry(address)
+            }
+
+            pub fn get(&self, address: ::ethers::prelude::Address) -> Option<#abi<M>> {
+                self.0.get_entry(address)
+            }
+
+            pub fn contains(&self, address: ::ethers::prelude::Address) -> bool {
+                self.0.entry_exists(address)
+            }
+
+            pub fn addresses(&self) -> Vec<::ethers::prelude::Address> {
+                self.0.addresses()
+            }
+
+            pub fn entries(&self) -> Vec<(::ethers::prelude::Address, #abi<M>)> {
+                self.0.entries()
+            }
+
+            /// Decodes calls and logs of registered addresses with this ABI,
+            /// including addresses registered after this call.
+            pub fn add_to_decoder(&self, decoder: &#krate::decode::TxDecoder)
+            where
+                M: 'static,
+            {
+                self.0
+                    .add_to_decoder(decoder, #name, |_| std::sync::Arc::clone(Self::base_contract()))
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0.is_empty()
+            }
+
+            pub fn clear(&self) {
+                self.0.clear()
+            }
+
+            pub fn set_metadata(
+                &self,
+                address: ::ethers::prelude::Address,
+                metadata: #krate::snapshot::EntryMetadata,
+            ) {
+                self.0.set_metadata(address, metadata)
+            }
+
+            pub fn metadata(
+                &self,
+                address: ::ethers::prelude::Address,
+            ) -> Option<#krate::snapshot::EntryMetadata> {
+                self.0.get_metadata(address)
+            }
+
+            pub fn snapshot(&self) -> #krate::snapshot::RegistrySnapshot {
+                self.0.snapshot(#name)
+            }
+
+            /// Re-registers every snapshot entry, along with its aliases and
+            /// metadata, using `provider`.
+            pub fn restore(
+                &self,
+                provider: M,
+                snapshot: &#krate::snapshot::RegistrySnapshot,
+            ) -> Result<Vec<#abi<M>>, #krate::error::Error>
+            where
+                M: Clone,
+            {
+                if snapshot.abi != #name {
+                    return Err(#krate::error::Error::AbiMismatch {
+                        expected: #name.to_string(),
+                        actual: snapshot.abi.clone(),
+                    });
+                }
+
+                let chain_id = self.0.chain.map(|chain| chain.id());
+                if let (Some(expected), Some(actual)) = (chain_id, snapshot.chain_id) {
+                    if expected != actual {
+                        return Err(#krate::error::Error::ChainIdMismatch {
+                            expected,
+                            actual: actual.into(),
+                        });
+                    }
+                }
+
+                let instances = snapshot
+                    .entries
+                    .iter()
+                    .map(|entry| {
+                        let instance = self.register(provider.clone(), entry.address);
+                        for alias in &entry.aliases {
+                            self.0.add_alias(alias.clone(), entry.address);
+                        }
+                        if !entry.metadata.is_empty() {
+                            self.0.set_metadata(entry.address, entry.metadata.clone());
+                        }
+                        instance
+                    })
+                    .collect();
+
+                Ok(instances)
+            }
+
+            pub fn chain(&self) -> Option<#krate::chain::Chain> {
+                self.0.chain
+            }
+
+            /// Copies the registry with every entry reading state at `block`.
+            /// Entries registered through the returned registry share its
+            /// pinned client.
+            pub fn at_block<B>(&self, provider: M, block: B) -> #pinned<M>
+            where
+                B: Into<::ethers::prelude::BlockId>,
+            {
+                let client =
+                    std::sync::Arc::new(#krate::pinned::BlockPinned::new(provider, block));
+                let pinned =
+                    #krate::registry::AbiRegistry::_new(self.0.url.clone(), self.0.chain)
+                        .with_config(self.0.config);
+                if let Some(capabilities) = self.0.capabilities() {
+                    pinned.set_capabilities(capabilities);
+                }
+
+                for address in self.0.addresses() {
+                    let instance = #abi::new(address, std::sync::Arc::clone(&client));
+                    pinned.add_entry(address, instance);
+                    if let Some(metadata) = self.0.get_metadata(address) {
+                        pinned.set_metadata(address, metadata);
+                    }
+                }
+                for (alias, address) in self.0.aliases() {
+                    pinned.add_alias(alias, address);
+                }
+
+                #pinned {
+                    registry: #registry(pinned),
+                    client,
+                }
+            }
+        }
+
+        /// Registry returned by `at_block`, holding the pinned client.
+        #[derive(Debug)]
+        #vis struct #pinned<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            registry: #registry<#krate::pinned::BlockPinned<M>>,
+            client: std::sync::Arc<#krate::pinned::BlockPinned<M>>,
+        }
+
+        impl<M> #pinned<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            pub fn client(&self) -> std::sync::Arc<#krate::pinned::BlockPinned<M>> {
+                std::sync::Arc::clone(&self.client)
+            }
+
+            pub fn block(&self) -> ::ethers::prelude::BlockId {
+                self.client.block()
+            }
+
+            pub fn register(
+                &self,
+                address: ::ethers::prelude::Address,
+            ) -> #abi<#krate::pinned::BlockPinned<M>> {
+                self.registry.0.get_or_insert_with(address, || {
+                    #abi::new(address, std::sync::Arc::clone(&self.client))
+                })
+            }
+
+            /// Multicall sending its aggregate call at the pinned block.
+            pub async fn multicall(
+                &self,
+                address: Option<::ethers::prelude::Address>,
+            ) -> Result<
+                ::ethers::contract::Multicall<#krate::pinned::BlockPinned<M>>,
+                #krate::error::Error,
+            > {
+                Ok(::ethers::contract::Multicall::new(self.client(), address).await?)
+            }
+
+            pub fn into_inner(self) -> #registry<#krate::pinned::BlockPinned<M>> {
+                self.registry
+            }
+        }
+
+        impl<M> std::ops::Deref for #pinned<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            type Target = #registry<#krate::pinned::BlockPinned<M>>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.registry
+            }
+        }
+
+        impl<M> #registry<M>
+        where
+            M: ::ethers::prelude::Middleware + Clone,
+            #krate::providers::AbiProvider: #krate::providers::AbiProviderTrait<M>,
+        {
+            /// Connects to the endpoint configured for `chain` and registers
+            /// the addresses of `aliases` from the chain's address book.
+            pub async fn from_config(
+                config: &#krate::config::Config,
+                chain: #krate::chain::Chain,
+                aliases: &[&str],
+            ) -> Result<Self, #krate::error::Error> {
+                let endpoint = config.endpoint(chain)?;
+                let addresses = aliases
+                    .iter()
+                    .map(|alias| match endpoint.addresses.get(*alias) {
+                        Some(address) => Ok((*alias, *address)),
+                        None => Err(#krate::error::Error::UnknownAlias {
+                            alias: alias.to_string(),
+                            chain_id: chain.id(),
+                        }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let abi_provider = endpoint.abi_provider();
+                let provider: M =
+                    #krate::providers::AbiProviderTrait::provider(&abi_provider).await?;
+
+                let registry = Self::new(endpoint.url.clone(), endpoint.chain);
+                if let Some(capabilities) = abi_provider.capabilities() {
+                    registry.0.set_capabilities(capabilities);
+                }
+                for (alias, address) in addresses {
+                    registry.register_as(provider.clone(), alias, address);
+                }
+
+                Ok(registry)
+            }
+        }
+    }
+}
+
+/// `AbiProviderTrait` implementation connecting `registry` over `transport`,
+/// one of `TRANSPORTS`.
+pub fn transport(krate: &Path, registry: &Ident, transport: &Ident) -> TokenStream {
+    if transport == "Mock" {
+        return quote! {
+            #[#krate::async_trait::async_trait]
+            impl #krate::providers::AbiProviderTrait<#krate::providers::MockProvider>
+                for #registry<#krate::providers::MockProvider>
+            {
+                async fn provider(
+                    &self,
+                ) -> Result<#krate::providers::MockProvider, #krate::error::Error> {
+                    let provider: #krate::providers::MockProvider =
+                        #krate::providers::AbiProvider::mock().provider().await?;
+
+                    Ok(provider)
+                }
+            }
+        };
+    }
+
+    let provider = format_ident!("{}Provider", transport);
+    quote! {
+        #[#krate::async_trait::async_trait]
+        impl #krate::providers::AbiProviderTrait<#krate::providers::#provider>
+            for #registry<#krate::providers::#provider>
+        {
+            async fn provider(
+                &self,
+            ) -> Result<#krate::providers::#provider, #krate::error::Error> {
+                let abi_provider = self.0.abi_provider();
+                let provider: #krate::providers::#provider =
+                    #krate::providers::AbiProviderTrait::provider(&abi_provider).await?;
+                if let Some(capabilities) = abi_provider.capabilities() {
+                    self.0.set_capabilities(capabilities);
+                }
+
+                Ok(provider)
+            }
+        }
+    }
+}
+
+/// Multi-chain registry wrapping `MultiChainRegistry<M, abi<M>>`.
+pub fn multichain(krate: &Path, vis: &Visibility, registry: &Ident, abi: &Ident) -> TokenStream {
+    quote! {
+        #[derive(Debug)]
+        #vis struct #registry<M>(
+            #krate::multichain::MultiChainRegistry<M, #abi<M>>,
+        )
+        where
+            M: ::ethers::prelude::Middleware;
+
+        impl<M> Default for #registry<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<M> #registry<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            pub fn new() -> Self {
+                Self(#krate::multichain::MultiChainRegistry::new())
+            }
+
+            pub fn add_provider(&self, chain: #krate::chain::Chain, provider: M) {
+                self.0.add_provider(chain, provider)
+            }
+
+            pub fn provider(&self, chain: #krate::chain::Chain) -> Option<std::sync::Arc<M>> {
+                self.0.provider(chain)
+            }
+
+            pub fn chains(&self) -> Vec<#krate::chain::Chain> {
+                self.0.chains()
+            }
+
+            pub fn capabilities(
+                &self,
+                chain: #krate::chain::Chain,
+            ) -> Option<#krate::capabilities::Capabilities> {
+                self.0.capabilities(chain)
+            }
+
+            pub fn register(
+                &self,
+                chain: #krate::chain::Chain,
+                address: ::ethers::prelude::Address,
+            ) -> Result<#abi<M>, #krate::error::Error> {
+                let provider = self
+                    .0
+                    .provider(chain)
+                    .ok_or(#krate::error::Error::UnknownChain { chain_id: chain.id() })?;
+
+                Ok(self.0.get_or_insert_with(chain, address, || #abi::new(address, provider)))
+            }
+
+            pub fn get(
+                &self,
+                chain: #krate::chain::Chain,
+                address: ::ethers::prelude::Address,
+            ) -> Option<#abi<M>> {
+                self.0.get_entry(chain, address)
+            }
+
+            pub fn deployments(
+                &self,
+                address: ::ethers::prelude::Address,
+            ) -> Vec<(#krate::chain::Chain, #abi<M>)> {
+                self.0.deployments(address)
+            }
+
+            pub fn entries(
+                &self,
+            ) -> Vec<((#krate::chain::Chain, ::ethers::prelude::Address), #abi<M>)> {
+                self.0.entries()
+            }
+        }
+
+        impl<M> #registry<M>
+        where
+            M: ::ethers::prelude::Middleware,
+            #krate::providers::AbiProvider: #krate::providers::AbiProviderTrait<M>,
+        {
+            pub async fn add_chain(
+                &self,
+                url: String,
+                chain: #krate::chain::Chain,
+            ) -> Result<(), #krate::error::Error> {
+                self.0.add_chain(url, chain).await
+            }
+
+            /// Connects to every configured endpoint and registers the addresses
+            /// of `aliases` found in each chain's address book.
+            pub async fn from_config(
+                config: &#krate::config::Config,
+                aliases: &[&str],
+            ) -> Result<Self, #krate::error::Error> {
+                let registry = Self::new();
+                for endpoint in config.chains.values() {
+                    registry.add_chain(endpoint.url.clone(), endpoint.chain).await?;
+                    for alias in aliases {
+                        if let Some(address) = endpoint.addresses.get(*alias) {
+                            registry.register(endpoint.chain, *address)?;
+                        }
+                    }
+                }
+
+                Ok(registry)
+            }
+        }
+    }
+}
+
+/// Helper methods and the `AbiContract` implementation on the contract type,
+/// which can only be generated once per contract.
+pub fn contract(krate: &Path, abi: &Ident, name: &str) -> TokenStream {
+    quote! {
+        impl<M> #abi<M>
+        where
+            M: ::ethers::prelude::Middleware,
+        {
+            pub async fn get_logs<E>(
+                &self,
+                from_block: ::ethers::prelude::BlockNumber,
+                to_block: ::ethers::prelude::BlockNumber,
+            ) -> Result<Vec<E>, #krate::error::Error>
+            where
+                E: ethers::prelude::EthEvent + std::fmt::Debug,
+            {
+                let res = self
+                    .event::<E>()
+                    .address(ethers::prelude::ValueOrArray::Value(self.address()))
+                    .from_block(from_block)
+                    .to_block(to_block)
+                    .query()
+                    .await?;
+
+                Ok(res)
+            }
+
+            pub async fn simulate<D>(
+                &self,
+                call: ::ethers::contract::ContractCall<M, D>,
+                overrides: &#krate::simulate::Overrides,
+            ) -> Result<#krate::simulate::Simulation<D>, #krate::error::Error>
+            where
+                M: 'static,
+                M::Inner: 'static,
+                D: ::ethers::abi::Detokenize,
+            {
+                #krate::simulate::simulate_call(self.client_ref(), &call, overrides).await
+            }
+
+            pub fn at_block<B>(&self, block: B) -> #abi<#krate::pinned::BlockPinned<M>>
+            where
+                B: Into<::ethers::prelude::BlockId>,
+            {
+                let client = #krate::pinned::BlockPinned::new(self.client(), block);
+                #abi::new(self.address(), std::sync::Arc::new(client))
+            }
+        }
+
+        impl<M> #krate::contract::AbiContract<M> for #abi<M>
+        where
+            M: ::ethers::prelude::Middleware + 'static,
+        {
+            const NAME: &'static str = #name;
+
+            fn from_address(
+                address: ::ethers::prelude::Address,
+                client: std::sync::Arc<M>,
+            ) -> Self {
+                #abi::new(address, client)
+            }
+
+            fn contract_address(&self) -> ::ethers::prelude::Address {
+                self.address()
+            }
+
+            fn contract_abi(&self) -> &::ethers::abi::Abi {
+                self.abi()
+            }
+        }
+    }
+}
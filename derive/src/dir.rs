@@ -0,0 +1,171 @@
+use {
+    crate::expr_bool,
+    ethers_abirpc_codegen::{
+        artifact::{collect_files, Artifact},
+        registry::abirpc,
+    },
+    proc_macro2::TokenStream as TokenStream2,
+    quote::quote,
+    serde_json::json,
+    std::{collections::BTreeMap, env, path::PathBuf},
+    syn::{
+        parse::{Parse, ParseStream},
+        punctuated::Punctuated,
+        Error, Expr, Ident, LitStr, Meta, Path, Token,
+    },
+};
+
+pub struct DirArgs {
+    dir: LitStr,
+    bytecode: bool,
+    contracts: Option<Vec<Ident>>,
+    krate: Path,
+}
+
+impl Parse for DirArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = DirArgs {
+            dir: input.parse()?,
+            bytecode: false,
+            contracts: None,
+            krate: syn::parse_quote!(::ethers_abirpc),
+        };
+        if input.is_empty() {
+            return Ok(args);
+        }
+        input.parse::<Token![,]>()?;
+
+        for meta in Punctuated::<Meta, Token![,]>::parse_terminated(input)? {
+            match &meta {
+                Meta::List(list) if list.path.is_ident("contracts") => {
+                    let contracts =
+                        list.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+                    args.contracts = Some(contracts.into_iter().collect());
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("bytecode") => {
+                    args.bytecode = expr_bool(&nv.value)?;
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("crate") => {
+                    args.krate = match &nv.value {
+                        Expr::Path(path) => path.path.clone(),
+                        value => return Err(Error::new_spanned(value, "expected a crate path")),
+                    };
+                }
+                meta => {
+                    return Err(Error::new_spanned(
+                        meta.path(),
+                        "unknown option, expected `bytecode = bool`, `contracts(..)` or \
+                         `crate = path`",
+                    ))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+pub fn expand(args: DirArgs) -> syn::Result<TokenStream2> {
+    let DirArgs {
+        dir,
+        bytecode,
+        contracts,
+        krate,
+    } = args;
+    let err = |msg: String| Error::new(dir.span(), msg);
+
+    let mut root = PathBuf::from(dir.value());
+    if root.is_relative() {
+        if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+            root = PathBuf::from(manifest_dir).join(root);
+        }
+    }
+    if !root.is_dir() {
+        return Err(err(format!(
+            "ABI directory `{}` does not exist",
+            dir.value()
+        )));
+    }
+
+    let mut files = vec![];
+    collect_files(&root, &mut files)
+        .map_err(|e| err(format!("failed to read `{}`: {e}", dir.value())))?;
+
+    let mut artifacts = BTreeMap::<String, Artifact>::new();
+    for path in files {
+        let display = path
+            .strip_prefix(&root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let Some(artifact) = Artifact::read(&path).map_err(|e| err(format!("`{display}`: {e}")))?
+        else {
+            continue;
+        };
+        let name = artifact.name.clone();
+        if let Some(contracts) = &contracts {
+            if !contracts.iter().any(|contract| contract == &name) {
+                continue;
+            }
+        }
+        if let Some(existing) = artifacts.get(&name) {
+            let existing = existing.path.strip_prefix(&root).unwrap_or(&existing.path);
+            return Err(err(format!(
+                "contract `{name}` is defined by both `{}` and `{display}`",
+                existing.display()
+            )));
+        }
+        artifacts.insert(name, artifact);
+    }
+
+    if let Some(contracts) = &contracts {
+        for contract in contracts {
+            if !artifacts.contains_key(&contract.to_string()) {
+                return Err(Error::new_spanned(
+                    contract,
+                    format!("no artifact found for `{contract}` in `{}`", dir.value()),
+                ));
+            }
+        }
+    }
+    if artifacts.is_empty() {
+        return Err(err(format!("no ABI files found in `{}`", dir.value())));
+    }
+
+    let mut tokens = TokenStream2::new();
+    for (name, artifact) in artifacts {
+        let display = artifact.path.strip_prefix(&root).unwrap_or(&artifact.path);
+        let ident = syn::parse_str::<Ident>(&name).map_err(|_| {
+            err(format!(
+                "`{}` is not a valid contract identifier, select artifacts with `contracts(..)`",
+                display.display()
+            ))
+        })?;
+
+        let bytecode = match bytecode {
+            true => artifact.linked_bytecode().map_err(|_| {
+                err(format!(
+                    "bytecode in `{}` has unlinked library references",
+                    display.display()
+                ))
+            })?,
+            false => None,
+        };
+        let source = match bytecode {
+            Some(bytecode) => json!({ "abi": artifact.abi, "bytecode": bytecode }),
+            None => artifact.abi,
+        };
+        let source = LitStr::new(&source.to_string(), dir.span());
+        let path = LitStr::new(&artifact.path.display().to_string(), dir.span());
+
+        let registry = abirpc(&krate, &ident);
+
+        tokens.extend(quote! {
+            ::ethers::contract::abigen!(#ident, #source);
+            #registry
+            const _: &[u8] = include_bytes!(#path);
+        });
+    }
+
+    Ok(tokens)
+}
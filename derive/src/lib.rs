@@ -0,0 +1,248 @@
+use {
+    proc_macro::TokenStream,
+    proc_macro2::{Span, TokenStream as TokenStream2},
+    quote::{format_ident, quote, quote_spanned},
+    syn::{
+        parse::{Parse, ParseStream},
+        parse_macro_input,
+        punctuated::Punctuated,
+        Error, Expr, ExprLit, Fields, Ident, ItemStruct, Lit, Meta, Path, Token,
+    },
+};
+
+mod dir;
+
+use ethers_abirpc_codegen::registry::{self as codegen, TRANSPORTS};
+
+struct RegistryArgs {
+    abi: Ident,
+    transports: Vec<Ident>,
+    multichain: Option<Ident>,
+    helpers: bool,
+    krate: Path,
+}
+
+impl Parse for RegistryArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+        let mut metas = metas.into_iter();
+
+        let abi = match metas.next() {
+            Some(Meta::Path(path)) => path.require_ident()?.clone(),
+            Some(meta) => return Err(Error::new_spanned(meta, "expected a contract identifier")),
+            None => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "expected a contract identifier, e.g. #[abirpc_registry(Erc20Token)]",
+                ))
+            }
+        };
+
+        let mut args = RegistryArgs {
+            abi,
+            transports: TRANSPORTS
+                .iter()
+                .map(|transport| Ident::new(transport, Span::call_site()))
+                .collect(),
+            multichain: None,
+            helpers: true,
+            krate: syn::parse_quote!(::ethers_abirpc),
+        };
+
+        for meta in metas {
+            match &meta {
+                Meta::List(list) if list.path.is_ident("transports") => {
+                    let transports =
+                        list.parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?;
+                    args.transports = vec![];
+                    for transport in transports {
+                        if !TRANSPORTS.contains(&transport.to_string().as_str()) {
+                            return Err(Error::new_spanned(
+                                &transport,
+                                format!(
+                                    "unknown transport, expected one of {}",
+                                    TRANSPORTS.join(", ")
+                                ),
+                            ));
+                        }
+                        if args.transports.contains(&transport) {
+                            return Err(Error::new_spanned(&transport, "duplicate transport"));
+                        }
+                        args.transports.push(transport);
+                    }
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("multichain") => {
+                    args.multichain = Some(expr_ident(&nv.value)?);
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("helpers") => {
+                    args.helpers = expr_bool(&nv.value)?;
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("crate") => {
+                    args.krate = match &nv.value {
+                        Expr::Path(path) => path.path.clone(),
+                        value => return Err(Error::new_spanned(value, "expected a crate path")),
+                    };
+                }
+                meta => {
+                    return Err(Error::new_spanned(
+                        meta.path(),
+                        "unknown option, expected `transports(..)`, `multichain = Name`, \
+                         `helpers = bool` or `crate = path`",
+                    ))
+                }
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn expr_ident(expr: &Expr) -> syn::Result<Ident> {
+    match expr {
+        Expr::Path(path) => Ok(path.path.require_ident()?.clone()),
+        expr => Err(Error::new_spanned(expr, "expected an identifier")),
+    }
+}
+
+fn expr_bool(expr: &Expr) -> syn::Result<bool> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Bool(value),
+            ..
+        }) => Ok(value.value),
+        expr => Err(Error::new_spanned(expr, "expected `true` or `false`")),
+    }
+}
+
+/// Generates an `abirpc!` style registry named after the annotated unit struct.
+///
+/// ```ignore
+/// abigen!(Erc20Token, "./abi/Erc20Token.json");
+///
+/// #[abirpc_registry(Erc20Token, transports(Http, Mock), multichain = TokenMultiChainRegistry)]
+/// pub struct TokenRegistry;
+/// ```
+///
+/// `helpers = false` skips the helper methods generated on the contract type,
+/// which must be generated once per contract.
+#[proc_macro_attribute]
+pub fn abirpc_registry(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as RegistryArgs);
+    let item = parse_macro_input!(item as ItemStruct);
+
+    match expand(args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(args: RegistryArgs, item: ItemStruct) -> syn::Result<TokenStream2> {
+    if !matches!(item.fields, Fields::Unit) {
+        return Err(Error::new_spanned(
+            &item.fields,
+            "expected a unit struct, the registry fields are generated",
+        ));
+    }
+    if !item.generics.params.is_empty() || item.generics.where_clause.is_some() {
+        return Err(Error::new_spanned(
+            &item.generics,
+            "expected a struct without generics, the middleware parameter is generated",
+        ));
+    }
+
+    let RegistryArgs {
+        abi,
+        transports,
+        multichain,
+        helpers,
+        krate,
+    } = args;
+    let ItemStruct {
+        attrs, vis, ident, ..
+    } = item;
+    if abi == ident {
+        return Err(Error::new_spanned(
+            &abi,
+            "expected the abigen contract type, not the registry being generated",
+        ));
+    }
+
+    // The generated code refers to the contract through an alias so that an
+    // unknown or non-abigen type is reported once, on the user's identifier.
+    let name = abi.to_string();
+    let alias = format_ident!("__{}Abi", ident);
+    let aliased = quote_spanned! {abi.span()=> #abi<M> };
+    let registry = codegen::registry(&krate, &attrs, &vis, &ident, &alias, &name);
+    let transports = transports
+        .iter()
+        .map(|transport| codegen::transport(&krate, &ident, transport));
+    let multichain =
+        multichain.map(|multichain| codegen::multichain(&krate, &vis, &multichain, &alias));
+    let helpers = helpers.then(|| codegen::contract(&krate, &alias, &name));
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #vis type #alias<M> = #aliased;
+        #registry
+        #(#transports)*
+        #multichain
+        #helpers
+    })
+}
+
+/// Crate path and identifier passed by `abirpc!` and the crate internals.
+struct InternalArgs {
+    krate: Path,
+    ident: Ident,
+}
+
+impl Parse for InternalArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let ident = input.parse()?;
+
+        Ok(InternalArgs { krate, ident })
+    }
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __abirpc(input: TokenStream) -> TokenStream {
+    let InternalArgs { krate, ident } = parse_macro_input!(input as InternalArgs);
+    codegen::abirpc(&krate, &ident).into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __abirpc_transports(input: TokenStream) -> TokenStream {
+    let InternalArgs { krate, ident } = parse_macro_input!(input as InternalArgs);
+    TRANSPORTS
+        .iter()
+        .map(|transport| {
+            codegen::transport(&krate, &ident, &Ident::new(transport, Span::call_site()))
+        })
+        .collect::<TokenStream2>()
+        .into()
+}
+
+/// Generates bindings and registries for every contract in a directory of
+/// JSON ABIs or Foundry/Hardhat artifacts. Relative paths are rooted at the
+/// crate manifest.
+///
+/// ```ignore
+/// abirpc_dir!("./out", bytecode = true, contracts(Counter, Vault));
+/// ```
+///
+/// Contracts are named after the artifact `contractName` or the file name.
+/// `bytecode = true` embeds artifact bytecode as `<NAME>_BYTECODE` for
+/// deployment, and `contracts(..)` limits generation to the listed contracts.
+#[proc_macro]
+pub fn abirpc_dir(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as dir::DirArgs);
+
+    match dir::expand(args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,105 @@
+use {
+    crate::{chain::Chain, error::Error},
+    ethers::types::Address,
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, fs, path::Path},
+};
+
+type Aliases = BTreeMap<String, Address>;
+
+/// Contract addresses keyed by chain and alias. Chains are written either as
+/// a chain id or as a [`crate::named::NamedChain`] name, e.g.
+///
+/// ```toml
+/// [mainnet]
+/// USDC = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+///
+/// [10]
+/// USDC = "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85"
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct AddressBook {
+    pub chains: BTreeMap<u64, Aliases>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(transparent)]
+struct RawAddressBook(BTreeMap<String, Aliases>);
+
+impl TryFrom<RawAddressBook> for AddressBook {
+    type Error = Error;
+
+    fn try_from(raw: RawAddressBook) -> Result<Self, Self::Error> {
+        let mut book = AddressBook::new();
+        for (chain, aliases) in raw.0 {
+            let chain_id = chain.parse::<Chain>()?.id();
+            book.chains.entry(chain_id).or_default().extend(aliases);
+        }
+
+        Ok(book)
+    }
+}
+
+impl AddressBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let raw: RawAddressBook = serde_json::from_str(json)?;
+        Self::try_from(raw)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        let raw: RawAddressBook = toml::from_str(toml)?;
+        Self::try_from(raw)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        let raw: RawAddressBook = serde_yaml_ng::from_str(yaml)?;
+        Self::try_from(raw)
+    }
+
+    /// Loads an address book, choosing the format from the file extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            Some("yaml") | Some("yml") => Self::from_yaml(&contents),
+            _ => Err(Error::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    pub fn insert(
+        &mut self,
+        chain: Chain,
+        alias: impl Into<String>,
+        address: Address,
+    ) -> Option<Address> {
+        self.chains
+            .entry(chain.id())
+            .or_default()
+            .insert(alias.into(), address)
+    }
+
+    pub fn get(&self, chain: Chain, alias: &str) -> Option<Address> {
+        self.chains.get(&chain.id())?.get(alias).copied()
+    }
+
+    pub fn addresses(&self, chain: Chain) -> Option<Vec<(String, Address)>> {
+        let aliases = self.chains.get(&chain.id())?;
+        let addresses = aliases
+            .iter()
+            .map(|(alias, address)| (alias.clone(), *address))
+            .collect();
+
+        Some(addresses)
+    }
+
+    pub fn chains(&self) -> Vec<Chain> {
+        self.chains.keys().copied().map(Chain::from).collect()
+    }
+}
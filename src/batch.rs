@@ -0,0 +1,296 @@
+use {
+    crate::chain::BatchClientConfig,
+    async_trait::async_trait,
+    ethers::providers::{JsonRpcClient, JsonRpcError, ProviderError, RpcError},
+    reqwest::Client,
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    serde_json::value::RawValue,
+    std::{
+        collections::HashMap,
+        fmt::Debug,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    },
+    thiserror::Error,
+    tokio::sync::oneshot,
+    url::Url,
+};
+
+#[derive(Error, Debug)]
+pub enum BatchClientError {
+    #[error(transparent)]
+    ReqwestError(Arc<reqwest::Error>),
+    #[error(transparent)]
+    JsonRpcError(#[from] JsonRpcError),
+    #[error("Deserialization Error: {err}. Response: {text}")]
+    SerdeJson {
+        err: Arc<serde_json::Error>,
+        text: String,
+    },
+    #[error("Batch response is missing request id {0}")]
+    MissingResponse(u64),
+    #[error("Batch request was dropped before a response was received")]
+    Dropped,
+}
+
+impl From<BatchClientError> for ProviderError {
+    fn from(src: BatchClientError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+impl RpcError for BatchClientError {
+    fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            BatchClientError::JsonRpcError(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    fn as_serde_error(&self) -> Option<&serde_json::Error> {
+        match self {
+            BatchClientError::SerdeJson { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+type BatchResult = Result<Box<RawValue>, BatchClientError>;
+
+#[derive(Debug)]
+struct PendingRequest {
+    id: u64,
+    method: String,
+    params: Box<RawValue>,
+    sender: oneshot::Sender<BatchResult>,
+}
+
+#[derive(Serialize)]
+struct RequestPayload<'a> {
+    id: u64,
+    jsonrpc: &'a str,
+    method: &'a str,
+    params: &'a RawValue,
+}
+
+#[derive(Deserialize)]
+struct ResponsePayload {
+    id: u64,
+    #[serde(default)]
+    result: Option<Box<RawValue>>,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct ErrorPayload {
+    error: JsonRpcError,
+}
+
+#[derive(Debug, Default)]
+struct BatchQueue {
+    pending: Vec<PendingRequest>,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct BatchInner {
+    id: AtomicU64,
+    client: Client,
+    url: Url,
+    config: BatchClientConfig,
+    queue: Mutex<BatchQueue>,
+}
+
+/// A JSON-RPC client over HTTP which coalesces requests issued within
+/// `BatchClientConfig::batch_window_ms` of each other into a single batch.
+#[derive(Debug, Clone)]
+pub struct BatchHttp {
+    inner: Arc<BatchInner>,
+}
+
+impl BatchHttp {
+    pub fn new(url: impl Into<Url>, config: BatchClientConfig) -> Self {
+        Self::new_with_client(url, config, Client::new())
+    }
+
+    pub fn new_with_client(url: impl Into<Url>, config: BatchClientConfig, client: Client) -> Self {
+        Self {
+            inner: Arc::new(BatchInner {
+                id: AtomicU64::new(1),
+                client,
+                url: url.into(),
+                config,
+                queue: Mutex::new(BatchQueue::default()),
+            }),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.inner.url
+    }
+
+    pub fn config(&self) -> BatchClientConfig {
+        self.inner.config
+    }
+
+    fn enqueue(&self, request: PendingRequest) {
+        let mut queue = self
+            .inner
+            .queue
+            .lock()
+            .expect("Batch queue Mutex poisoned!");
+        queue.pending.push(request);
+
+        if queue.pending.len() >= self.inner.config.max_batch_size {
+            let batch = std::mem::take(&mut queue.pending);
+            queue.generation += 1;
+            drop(queue);
+
+            tokio::spawn(Arc::clone(&self.inner).send_batch(batch));
+        } else if queue.pending.len() == 1 {
+            let generation = queue.generation;
+            drop(queue);
+
+            let inner = Arc::clone(&self.inner);
+            let window = Duration::from_millis(inner.config.batch_window_ms);
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                inner.flush(generation).await;
+            });
+        }
+    }
+}
+
+impl BatchInner {
+    async fn flush(self: Arc<Self>, generation: u64) {
+        let batch = {
+            let mut queue = self.queue.lock().expect("Batch queue Mutex poisoned!");
+            if queue.generation != generation {
+                return;
+            }
+            queue.generation += 1;
+            std::mem::take(&mut queue.pending)
+        };
+
+        self.send_batch(batch).await;
+    }
+
+    async fn send_batch(self: Arc<Self>, batch: Vec<PendingRequest>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let payload: Vec<RequestPayload> = batch
+            .iter()
+            .map(|request| RequestPayload {
+                id: request.id,
+                jsonrpc: "2.0",
+                method: &request.method,
+                params: &request.params,
+            })
+            .collect();
+
+        let body = match self
+            .client
+            .post(self.url.as_ref())
+            .json(&payload)
+            .send()
+            .await
+        {
+            Ok(res) => res.bytes().await,
+            Err(err) => Err(err),
+        };
+
+        let body = match body {
+            Ok(body) => body,
+            Err(err) => {
+                let err = Arc::new(err);
+                for request in batch {
+                    let _ = request
+                        .sender
+                        .send(Err(BatchClientError::ReqwestError(Arc::clone(&err))));
+                }
+                return;
+            }
+        };
+
+        let mut responses = match serde_json::from_slice::<Vec<ResponsePayload>>(&body) {
+            Ok(responses) => responses
+                .into_iter()
+                .map(|response| (response.id, response))
+                .collect::<HashMap<_, _>>(),
+            Err(err) => {
+                // Endpoints without batch support commonly reply with a single error object
+                let text = String::from_utf8_lossy(&body).to_string();
+                let single = serde_json::from_slice::<ErrorPayload>(&body);
+                let err = Arc::new(err);
+                for request in batch {
+                    let res = match &single {
+                        Ok(payload) => BatchClientError::JsonRpcError(payload.error.clone()),
+                        Err(_) => BatchClientError::SerdeJson {
+                            err: Arc::clone(&err),
+                            text: text.clone(),
+                        },
+                    };
+                    let _ = request.sender.send(Err(res));
+                }
+                return;
+            }
+        };
+
+        for request in batch {
+            let res = match responses.remove(&request.id) {
+                Some(ResponsePayload {
+                    error: Some(error), ..
+                }) => Err(BatchClientError::JsonRpcError(error)),
+                Some(ResponsePayload {
+                    result: Some(result),
+                    ..
+                }) => Ok(result),
+                Some(_) => {
+                    Ok(RawValue::from_string(String::from("null")).expect("null is valid JSON"))
+                }
+                None => Err(BatchClientError::MissingResponse(request.id)),
+            };
+            let _ = request.sender.send(res);
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for BatchHttp {
+    type Error = BatchClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, BatchClientError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::value::to_raw_value(&params).map_err(|err| {
+            BatchClientError::SerdeJson {
+                err: Arc::new(err),
+                text: format!("{params:?}"),
+            }
+        })?;
+
+        let (sender, receiver) = oneshot::channel();
+        self.enqueue(PendingRequest {
+            id: self.inner.id.fetch_add(1, Ordering::SeqCst),
+            method: method.to_string(),
+            params,
+            sender,
+        });
+
+        let raw = receiver.await.map_err(|_| BatchClientError::Dropped)??;
+        let res = serde_json::from_str(raw.get()).map_err(|err| BatchClientError::SerdeJson {
+            err: Arc::new(err),
+            text: raw.to_string(),
+        })?;
+
+        Ok(res)
+    }
+}
@@ -0,0 +1,168 @@
+use {
+    crate::{print_json, NetworkArgs},
+    clap::Args,
+    ethers::types::{Address, Filter, TransactionReceipt},
+    ethers_abirpc::prelude::*,
+    serde::Serialize,
+    serde_json::Value,
+    std::{fs, path::PathBuf, sync::Arc},
+};
+
+/// Blocks searched by `logs` when `--from-block` is not set, as providers
+/// reject or throttle `eth_getLogs` over long ranges.
+const DEFAULT_LOG_WINDOW: u64 = 1_000;
+
+#[derive(Debug, Args)]
+pub struct ContractArgs {
+    /// Contract ABI JSON or Foundry/Hardhat artifact
+    #[arg(long)]
+    abi: PathBuf,
+    /// Contract address
+    #[arg(long)]
+    address: Address,
+}
+
+impl ContractArgs {
+    fn contract<M: Middleware>(&self, client: Arc<M>) -> Result<DynamicContract<M>, Error> {
+        let abi: Value = serde_json::from_str(&fs::read_to_string(&self.abi)?)?;
+        let abi = match abi {
+            Value::Object(mut artifact) => artifact.remove("abi").unwrap_or_default(),
+            abi => abi,
+        };
+
+        Ok(DynamicContract::new(
+            self.address,
+            serde_json::from_value(abi)?,
+            client,
+        ))
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct CallArgs {
+    #[command(flatten)]
+    contract: ContractArgs,
+    /// Function name or signature, e.g. `balanceOf` or `transfer(address,uint256)`
+    function: String,
+    /// Arguments as plain values or JSON arrays and strings
+    args: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct SendArgs {
+    #[command(flatten)]
+    contract: ContractArgs,
+    /// Encrypted JSON keystore
+    #[arg(long)]
+    keystore: PathBuf,
+    /// File containing the keystore password, prompted for when not set
+    #[arg(long, env = "ABIRPC_PASSWORD_FILE")]
+    password_file: Option<PathBuf>,
+    /// Value to send in wei
+    #[arg(long)]
+    value: Option<U256>,
+    /// Function name or signature
+    function: String,
+    /// Arguments as plain values or JSON arrays and strings
+    args: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct LogsArgs {
+    #[command(flatten)]
+    contract: ContractArgs,
+    /// Event name or signature
+    event: String,
+    /// First block searched, defaults to the 1000 blocks ending at `--to-block`
+    #[arg(long)]
+    from_block: Option<u64>,
+    /// Last block searched, defaults to the latest block
+    #[arg(long)]
+    to_block: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct SendOutput {
+    receipt: TransactionReceipt,
+    events: Vec<DecodedEvent>,
+}
+
+/// Arrays, objects, quoted strings and booleans are read as JSON, everything
+/// else is passed as a string so large integers keep their precision.
+fn parse_args(args: &[String]) -> Vec<Value> {
+    args.iter()
+        .map(|arg| {
+            let json = arg.starts_with(['[', '{', '"']) || arg == "true" || arg == "false";
+            match json {
+                true => serde_json::from_str(arg).unwrap_or_else(|_| Value::String(arg.clone())),
+                false => Value::String(arg.clone()),
+            }
+        })
+        .collect()
+}
+
+pub async fn call(network: &NetworkArgs, args: CallArgs) -> Result<(), Error> {
+    let provider = network.provider().await?;
+    let contract = args.contract.contract(Arc::new(provider))?;
+    let output = contract
+        .call(&args.function, &parse_args(&args.args))
+        .await?;
+
+    print_json(&output)
+}
+
+pub async fn send(network: &NetworkArgs, args: SendArgs) -> Result<(), Error> {
+    let password = match &args.password_file {
+        Some(path) => fs::read_to_string(path)?.trim_end().to_string(),
+        None => rpassword::prompt_password("Keystore password: ")?,
+    };
+    let wallet = LocalWallet::decrypt_keystore(&args.keystore, password)?
+        .with_chain_id(network.chain().id());
+
+    let provider = network.provider().await?;
+    let client = Arc::new(SignerMiddleware::new(provider, wallet));
+    let contract = args.contract.contract(Arc::clone(&client))?;
+
+    let mut tx = contract.transaction(&args.function, &parse_args(&args.args))?;
+    if let Some(value) = args.value {
+        tx.set_value(value);
+    }
+    let pending = client
+        .send_transaction(tx, None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    let tx_hash = pending.tx_hash();
+    let receipt = pending
+        .await?
+        .ok_or(Error::TransactionDropped { tx_hash })?;
+
+    let events = receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == contract.address())
+        .filter_map(|log| contract.decode_log(log).ok())
+        .collect();
+
+    print_json(&SendOutput { receipt, events })
+}
+
+pub async fn logs(network: &NetworkArgs, args: LogsArgs) -> Result<(), Error> {
+    let provider = Arc::new(network.provider().await?);
+    let to_block = match args.to_block {
+        Some(to_block) => to_block,
+        None => provider
+            .get_block_number()
+            .await
+            .map_err(Error::from_middleware_error)?
+            .as_u64(),
+    };
+    let from_block = args
+        .from_block
+        .unwrap_or_else(|| to_block.saturating_sub(DEFAULT_LOG_WINDOW - 1));
+    let contract = args.contract.contract(provider)?;
+
+    let filter = Filter::new().from_block(from_block).to_block(to_block);
+    let events = contract.events(&args.event, filter).await?;
+
+    print_json(&events)
+}
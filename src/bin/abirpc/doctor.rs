@@ -0,0 +1,260 @@
+use {
+    crate::{print_json, NetworkArgs},
+    clap::Args,
+    ethers::types::{BlockNumber, U256},
+    ethers_abirpc::{
+        capabilities::{probe_debug, probe_logs_range, probe_method, probe_trace},
+        prelude::*,
+    },
+    serde::Serialize,
+    serde_json::json,
+    std::{
+        fmt,
+        future::Future,
+        sync::Arc,
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+};
+
+const LATENCY_SAMPLES: usize = 5;
+// Head blocks older than this many block times are reported as stale
+const STALE_BLOCKS: u32 = 10;
+const MIN_STALE_SECS: u64 = 60;
+
+#[derive(Debug, Args)]
+pub struct DoctorArgs {
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    url: String,
+    chain_id: u64,
+    chain: Option<&'static str>,
+    checks: Vec<Check>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+            latency_ms: None,
+        }
+    }
+
+    fn latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.chain {
+            Some(chain) => writeln!(f, "{} (expecting {chain}, {})", self.url, self.chain_id)?,
+            None => writeln!(f, "{} (expecting chain {})", self.url, self.chain_id)?,
+        }
+        for check in &self.checks {
+            let status = match check.status {
+                Status::Ok => "ok",
+                Status::Warn => "warn",
+                Status::Fail => "FAIL",
+            };
+            write!(f, "  [{status:>4}] {:<12} {}", check.name, check.detail)?;
+            if let Some(latency) = check.latency_ms {
+                write!(f, " ({latency}ms)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+async fn timed<F: Future>(future: F) -> (F::Output, Duration) {
+    let start = Instant::now();
+    let output = future.await;
+    (output, start.elapsed())
+}
+
+async fn check_chain_id(provider: &RetryProvider, expected: u64) -> Check {
+    match timed(provider.get_chainid()).await {
+        (Ok(actual), latency) if actual == U256::from(expected) => {
+            Check::new("chain id", Status::Ok, actual.to_string()).latency(latency)
+        }
+        (Ok(actual), latency) => Check::new(
+            "chain id",
+            Status::Fail,
+            Error::ChainIdMismatch { expected, actual }.to_string(),
+        )
+        .latency(latency),
+        (Err(e), _) => Check::new("chain id", Status::Fail, e.to_string()),
+    }
+}
+
+async fn check_head(provider: &RetryProvider, chain: &Chain) -> (Check, Option<u64>) {
+    let block = match provider.get_block(BlockNumber::Latest).await {
+        Ok(Some(block)) => block,
+        Ok(None) => {
+            return (
+                Check::new("head block", Status::Fail, "no latest block"),
+                None,
+            )
+        }
+        Err(e) => return (Check::new("head block", Status::Fail, e.to_string()), None),
+    };
+    let number = block.number.unwrap_or_default().as_u64();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let age = now.saturating_sub(block.timestamp.as_u64());
+    let blocktime = chain.named().and_then(NamedChain::average_blocktime_hint);
+    let limit = blocktime
+        .map(|blocktime| (blocktime * STALE_BLOCKS).as_secs())
+        .unwrap_or_default()
+        .max(MIN_STALE_SECS);
+
+    let mut detail = format!("block {number}, {age}s old");
+    if let Some(blocktime) = blocktime {
+        detail.push_str(&format!(", block time {}ms", blocktime.as_millis()));
+    }
+    let status = match age > limit {
+        true => Status::Warn,
+        false => Status::Ok,
+    };
+
+    (Check::new("head block", status, detail), Some(number))
+}
+
+async fn check_archive(provider: &Arc<RetryProvider>) -> Check {
+    let pinned = BlockPinned::<RetryProvider>::new(Arc::clone(provider), 1u64);
+    match timed(pinned.check_archive()).await {
+        (Ok(()), latency) => {
+            Check::new("archive", Status::Ok, "state at block 1 available").latency(latency)
+        }
+        (Err(Error::ArchiveNodeRequired { message, .. }), _) => Check::new(
+            "archive",
+            Status::Warn,
+            format!("not an archive node: {message}"),
+        ),
+        (Err(e), _) => Check::new("archive", Status::Warn, e.to_string()),
+    }
+}
+
+/// Method support reported by a `capabilities` probe.
+async fn check_method<F>(name: &'static str, probe: F) -> Check
+where
+    F: Future<Output = Result<bool, Error>>,
+{
+    match timed(probe).await {
+        (Ok(true), latency) => Check::new(name, Status::Ok, "supported").latency(latency),
+        (Ok(false), _) => Check::new(name, Status::Warn, "not supported"),
+        (Err(e), _) => Check::new(name, Status::Fail, e.to_string()),
+    }
+}
+
+async fn check_logs_range(provider: &RetryProvider, head: u64) -> Check {
+    match timed(probe_logs_range(provider, head)).await {
+        (Some(range), latency) => {
+            Check::new("logs range", Status::Ok, format!("{range} blocks")).latency(latency)
+        }
+        (None, _) => Check::new(
+            "logs range",
+            Status::Warn,
+            "eth_getLogs failed for every range",
+        ),
+    }
+}
+
+async fn check_latency(provider: &RetryProvider) -> Check {
+    let mut samples = vec![];
+    for _ in 0..LATENCY_SAMPLES {
+        match timed(provider.get_block_number()).await {
+            (Ok(_), latency) => samples.push(latency.as_millis() as u64),
+            (Err(e), _) => return Check::new("latency", Status::Fail, e.to_string()),
+        }
+    }
+    samples.sort_unstable();
+
+    let min = samples[0];
+    let median = samples[samples.len() / 2];
+    let max = samples[samples.len() - 1];
+    Check::new(
+        "latency",
+        Status::Ok,
+        format!("min {min}ms, median {median}ms, max {max}ms over {LATENCY_SAMPLES} requests"),
+    )
+}
+
+pub async fn doctor(network: &NetworkArgs, args: DoctorArgs) -> Result<(), Error> {
+    // The chain id is checked below, so mismatches are reported instead of
+    // failing provider construction.
+    let chain = network.chain();
+    let provider: RetryProvider = AbiProvider::new(network.url()?, network.chain_config(false))
+        .provider()
+        .await?;
+    let provider = Arc::new(provider);
+
+    let mut checks = vec![check_chain_id(&provider, chain.id()).await];
+    let (head, number) = check_head(&provider, &chain).await;
+    checks.push(head);
+    checks.push(check_archive(&provider).await);
+
+    let fee_history = probe_method(
+        provider.as_ref(),
+        "eth_feeHistory",
+        json!(["0x1", "latest", [50]]),
+    );
+    checks.push(check_method("fee history", fee_history).await);
+    checks.push(check_method("debug", probe_debug(provider.as_ref())).await);
+    checks.push(check_method("trace", probe_trace(provider.as_ref())).await);
+    if let Some(number) = number {
+        checks.push(check_logs_range(&provider, number).await);
+    }
+    checks.push(check_latency(&provider).await);
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == Status::Fail)
+        .map(|check| check.name.to_string())
+        .collect::<Vec<_>>();
+    let report = Report {
+        url: network.url()?,
+        chain_id: chain.id(),
+        chain: chain.named().map(|named| named.as_str()),
+        checks,
+    };
+    match args.json {
+        true => print_json(&report)?,
+        false => print!("{report}"),
+    }
+
+    if !failed.is_empty() {
+        return Err(Error::ChecksFailed {
+            url: report.url,
+            checks: failed,
+        });
+    }
+    Ok(())
+}
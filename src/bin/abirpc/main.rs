@@ -0,0 +1,107 @@
+use {
+    clap::{Args, Parser, Subcommand},
+    ethers_abirpc::prelude::*,
+    serde::Serialize,
+};
+
+mod contract;
+mod doctor;
+
+/// Call contracts through ethers-abirpc providers and print the results as JSON.
+#[derive(Debug, Parser)]
+#[command(name = "abirpc", version)]
+struct Cli {
+    #[command(flatten)]
+    network: NetworkArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct NetworkArgs {
+    /// HTTP RPC endpoint
+    #[arg(long, env = "ABIRPC_RPC_URL", global = true)]
+    rpc_url: Option<String>,
+    /// Chain id or name, e.g. `1` or `mainnet`
+    #[arg(long, env = "ABIRPC_CHAIN", global = true, default_value = "mainnet")]
+    chain: Chain,
+    /// Retries after a rate limit response
+    #[arg(long, global = true)]
+    rate_limit_retries: Option<u32>,
+    /// Retries after a request timeout
+    #[arg(long, global = true)]
+    timeout_retries: Option<u32>,
+    /// Initial retry backoff in milliseconds
+    #[arg(long, global = true)]
+    initial_backoff_ms: Option<u64>,
+}
+
+impl NetworkArgs {
+    fn url(&self) -> Result<String, Error> {
+        self.rpc_url.clone().ok_or(Error::MissingUrl {
+            transport: Transport::Retry,
+        })
+    }
+
+    /// Chain with the retry overrides applied. The provider chain id is always
+    /// checked against the configured chain.
+    fn chain(&self) -> Chain {
+        self.chain_config(true)
+    }
+
+    fn chain_config(&self, assert_chain_id: bool) -> Chain {
+        let defaults = self.chain.retry_client_config();
+        let retry_client_config = RetryClientConfig {
+            rate_limit_retries: self
+                .rate_limit_retries
+                .unwrap_or(defaults.rate_limit_retries),
+            timeout_retries: self.timeout_retries.unwrap_or(defaults.timeout_retries),
+            initial_backoff_ms: self
+                .initial_backoff_ms
+                .unwrap_or(defaults.initial_backoff_ms),
+        };
+
+        Chain::ChainConfig(ChainConfig {
+            chain_id: self.chain.id(),
+            retry_client_config,
+            batch_client_config: self.chain.batch_client_config(),
+            assert_chain_id,
+            probe_capabilities: false,
+        })
+    }
+
+    async fn provider(&self) -> Result<RetryProvider, Error> {
+        AbiProvider::new(self.url()?, self.chain()).provider().await
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Call a view function
+    Call(contract::CallArgs),
+    /// Send a transaction signed with a keystore
+    Send(contract::SendArgs),
+    /// Fetch and decode event logs
+    Logs(contract::LogsArgs),
+    /// Check an endpoint against the expected chain
+    Doctor(doctor::DoctorArgs),
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Error> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Call(args) => contract::call(&cli.network, args).await?,
+        Command::Send(args) => contract::send(&cli.network, args).await?,
+        Command::Logs(args) => contract::logs(&cli.network, args).await?,
+        Command::Doctor(args) => doctor::doctor(&cli.network, args).await?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use {
+    crate::error::Error,
+    ethers::contract::Abigen,
+    ethers_abirpc_codegen::{
+        artifact::{collect_files, Artifact},
+        registry::abirpc,
+    },
+    serde_json::json,
+    std::{
+        collections::BTreeMap,
+        env, fs,
+        path::{Path, PathBuf},
+    },
+    syn::Ident,
+};
+
+/// Build script generator for contract bindings and registries.
+///
+/// ```ignore
+/// // build.rs
+/// RegistryBuilder::new()
+///     .dir("./abi")
+///     .contract("Vault", "./out/Vault.sol/Vault.json")
+///     .bytecode(true)
+///     .write_to_out_dir("contracts")?;
+///
+/// // lib.rs
+/// pub mod contracts {
+///     include!(concat!(env!("OUT_DIR"), "/contracts/mod.rs"));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistryBuilder {
+    contracts: Vec<(Option<String>, PathBuf)>,
+    dirs: Vec<PathBuf>,
+    bytecode: bool,
+    crate_path: String,
+}
+
+impl Default for RegistryBuilder {
+    fn default() -> Self {
+        Self {
+            contracts: vec![],
+            dirs: vec![],
+            bytecode: false,
+            crate_path: String::from("::ethers_abirpc"),
+        }
+    }
+}
+
+impl RegistryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a JSON ABI or Foundry/Hardhat artifact under the given contract name.
+    pub fn contract(mut self, name: impl Into<String>, path: impl AsRef<Path>) -> Self {
+        self.contracts
+            .push((Some(name.into()), path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Adds every JSON ABI and artifact in a directory, named after the
+    /// artifact `contractName` or the file name.
+    pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.dirs.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Embeds artifact bytecode as `<NAME>_BYTECODE` for deployment.
+    pub fn bytecode(mut self, bytecode: bool) -> Self {
+        self.bytecode = bytecode;
+        self
+    }
+
+    /// Path to `ethers_abirpc` used by the generated code.
+    pub fn crate_path(mut self, crate_path: impl Into<String>) -> Self {
+        self.crate_path = crate_path.into();
+        self
+    }
+
+    /// Generates the abigen bindings and the expanded registry code for every
+    /// contract, so nothing is left for macros to expand in the consuming crate.
+    pub fn generate(self) -> Result<RegistryBindings, Error> {
+        let krate = syn::parse_str::<syn::Path>(&self.crate_path)
+            .map_err(|e| Error::InvalidArgument(format!("crate path {}: {e}", self.crate_path)))?;
+
+        let mut files = vec![];
+        for (name, path) in &self.contracts {
+            files.push((name.clone(), resolve(path)));
+        }
+        for dir in &self.dirs {
+            let mut dir_files = vec![];
+            collect_files(&resolve(dir), &mut dir_files)?;
+            files.extend(dir_files.into_iter().map(|path| (None, path)));
+        }
+
+        let mut modules = BTreeMap::<String, ModuleBindings>::new();
+        let mut names = BTreeMap::<String, PathBuf>::new();
+        for (name, path) in files {
+            let Some(artifact) = Artifact::read(&path)? else {
+                if name.is_some() {
+                    return Err(Error::InvalidArtifact { path });
+                }
+                continue;
+            };
+            let name = name.unwrap_or_else(|| artifact.name.clone());
+            if let Some(first) = names.insert(name.clone(), path.clone()) {
+                return Err(Error::DuplicateContract {
+                    name,
+                    first,
+                    second: path,
+                });
+            }
+            let ident = syn::parse_str::<Ident>(&name).map_err(|_| Error::InvalidContractName {
+                name: name.clone(),
+                path: path.clone(),
+            })?;
+
+            let bytecode = match self.bytecode {
+                true => artifact
+                    .linked_bytecode()
+                    .map_err(|_| Error::UnlinkedBytecode { path: path.clone() })?,
+                false => None,
+            };
+            let source = match &bytecode {
+                Some(bytecode) => json!({ "abi": artifact.abi, "bytecode": bytecode }),
+                None => artifact.abi,
+            };
+
+            let bindings = Abigen::new(&name, source.to_string())
+                .and_then(|abigen| abigen.generate())
+                .map_err(|e| Error::AbigenError(format!("{name}: {e}")))?;
+            let registry = syn::parse2::<syn::File>(abirpc(&krate, &ident))
+                .map_err(|e| Error::AbigenError(format!("{name}: {e}")))?;
+            let code = format!("{bindings}\n{}", prettyplease::unparse(&registry));
+
+            let module = bindings.module_name();
+            if let Some(first) = modules.get(&module) {
+                return Err(Error::DuplicateModule {
+                    module,
+                    first: first.source.clone(),
+                    second: path,
+                });
+            }
+            modules.insert(
+                module,
+                ModuleBindings {
+                    name,
+                    source: path,
+                    bytecode: bytecode.is_some(),
+                    code,
+                },
+            );
+        }
+
+        Ok(RegistryBindings { modules })
+    }
+
+    /// Generates the bindings into `$OUT_DIR/<module>` and emits
+    /// `rerun-if-changed` directives for every source.
+    pub fn write_to_out_dir(self, module: &str) -> Result<PathBuf, Error> {
+        let out_dir = env::var("OUT_DIR").map_err(|_| Error::MissingOutDir)?;
+
+        for (_, path) in &self.contracts {
+            println!("cargo:rerun-if-changed={}", resolve(path).display());
+        }
+        for dir in &self.dirs {
+            println!("cargo:rerun-if-changed={}", resolve(dir).display());
+        }
+
+        let dir = PathBuf::from(out_dir).join(module);
+        self.generate()?.write_to_module(&dir)?;
+
+        Ok(dir)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ModuleBindings {
+    name: String,
+    source: PathBuf,
+    bytecode: bool,
+    code: String,
+}
+
+/// Generated bindings and registries, one module per contract.
+#[derive(Debug, Clone)]
+pub struct RegistryBindings {
+    modules: BTreeMap<String, ModuleBindings>,
+}
+
+impl RegistryBindings {
+    /// Contract names and the files they were generated from.
+    pub fn contracts(&self) -> Vec<(String, PathBuf)> {
+        self.modules
+            .values()
+            .map(|module| (module.name.clone(), module.source.clone()))
+            .collect()
+    }
+
+    /// Generated code for a contract module, including the expanded registry.
+    pub fn module(&self, module: &str) -> Option<&str> {
+        self.modules.get(module).map(|module| module.code.as_str())
+    }
+
+    /// Writes `mod.rs` and one file per contract to `dir`. Modules are declared
+    /// with absolute `#[path]` attributes so `mod.rs` can be `include!`d, and
+    /// the contract and registry types are re-exported from the root.
+    pub fn write_to_module(&self, dir: impl AsRef<Path>) -> Result<(), Error> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let dir = dir.canonicalize()?;
+
+        let mut root = String::from("// This file is generated by ethers-abirpc, do not edit.\n");
+        for (module, bindings) in &self.modules {
+            let file = dir.join(format!("{module}.rs"));
+            fs::write(&file, &bindings.code)?;
+
+            let name = &bindings.name;
+            let upper = name.to_uppercase();
+            let bytecode = if bindings.bytecode {
+                format!(", {upper}_BYTECODE")
+            } else {
+                String::new()
+            };
+            root.push_str(&format!(
+                "\n#[path = {:?}]\npub mod {module};\n",
+                file.display().to_string()
+            ));
+            root.push_str(&format!(
+                "pub use {module}::{{{name}, {name}Registry, {name}RegistryPinned, \
+                 {name}MultiChainRegistry, {upper}_ABI{bytecode}}};\n"
+            ));
+        }
+
+        fs::write(dir.join("mod.rs"), root)?;
+        Ok(())
+    }
+}
+
+// Relative paths are rooted at the crate manifest, matching `abigen!`
+fn resolve(path: &Path) -> PathBuf {
+    match env::var("CARGO_MANIFEST_DIR") {
+        Ok(manifest_dir) if path.is_relative() => PathBuf::from(manifest_dir).join(path),
+        _ => path.to_path_buf(),
+    }
+}
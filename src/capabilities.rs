@@ -0,0 +1,166 @@
+use {
+    crate::{batch::BatchHttp, error::Error, pinned::BlockPinned, providers::Transport},
+    ethers::{
+        providers::{JsonRpcClient, Middleware},
+        types::{Address, BlockNumber, Filter, H256, U256},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::{json, Value},
+};
+
+/// Block ranges tried when probing the `eth_getLogs` limit, largest first.
+pub const LOG_RANGES: [u64; 7] = [100_000, 10_000, 5_000, 2_000, 1_000, 100, 10];
+
+// Invalid input, resource not found and invalid params, returned by methods
+// which ran and rejected the probe's placeholder parameters
+const DOMAIN_ERROR_CODES: [i64; 3] = [-32000, -32001, -32602];
+
+/// Features supported by an endpoint. Providers built for a chain with
+/// `ChainConfig::probe_capabilities` set probe their endpoint once, see
+/// `AbiProvider::capabilities`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// State at block 1 is available.
+    pub archive: bool,
+    /// Largest block range out of `LOG_RANGES` accepted by `eth_getLogs`.
+    pub max_logs_range: Option<u64>,
+    /// JSON-RPC batch arrays are accepted.
+    pub batch: bool,
+    pub subscriptions: bool,
+    /// `debug_*` namespace is enabled.
+    pub debug: bool,
+    /// `trace_*` namespace is enabled.
+    pub trace: bool,
+    /// Latest block has a base fee.
+    pub eip1559: bool,
+}
+
+impl Capabilities {
+    /// Probes the endpoint behind `client`. Batch support is checked through
+    /// `batch`, which is only given for HTTP transports.
+    pub async fn probe<M>(client: &M, transport: Transport, batch: Option<&BatchHttp>) -> Self
+    where
+        M: Middleware,
+    {
+        let head = async {
+            let head = client.get_block(BlockNumber::Latest).await.ok().flatten();
+            let eip1559 = head
+                .as_ref()
+                .is_some_and(|block| block.base_fee_per_gas.is_some());
+            let max_logs_range = match head.and_then(|block| block.number) {
+                Some(number) => probe_logs_range(client, number.as_u64()).await,
+                None => None,
+            };
+
+            (eip1559, max_logs_range)
+        };
+        let archive = async { BlockPinned::new(client, 1u64).check_archive().await.is_ok() };
+        let batch = async {
+            match batch {
+                Some(batch) => probe_batch(batch).await,
+                None => false,
+            }
+        };
+        let subscriptions = async {
+            match transport {
+                Transport::Ws | Transport::Ipc => probe_subscriptions(client).await,
+                _ => false,
+            }
+        };
+
+        let ((eip1559, max_logs_range), archive, batch, subscriptions, debug, trace) = tokio::join!(
+            head,
+            archive,
+            batch,
+            subscriptions,
+            probe_debug(client),
+            probe_trace(client),
+        );
+
+        Self {
+            archive,
+            max_logs_range,
+            batch,
+            subscriptions,
+            debug: debug.unwrap_or(false),
+            trace: trace.unwrap_or(false),
+            eip1559,
+        }
+    }
+}
+
+/// Largest range out of `LOG_RANGES` accepted by `eth_getLogs` for the blocks
+/// ending at `head`.
+pub async fn probe_logs_range<M: Middleware>(client: &M, head: u64) -> Option<u64> {
+    for range in LOG_RANGES.into_iter().filter(|range| *range <= head) {
+        // No logs are emitted by the zero address so only the range is limited
+        let filter = Filter::new()
+            .address(Address::zero())
+            .from_block(head - range + 1)
+            .to_block(head);
+        if client.get_logs(&filter).await.is_ok() {
+            return Some(range);
+        }
+    }
+
+    None
+}
+
+/// `BatchHttp` sends every request as a batch array, so a single request
+/// fails on endpoints without batch support.
+async fn probe_batch(batch: &BatchHttp) -> bool {
+    batch.request::<_, U256>("eth_chainId", ()).await.is_ok()
+}
+
+async fn probe_subscriptions<M: Middleware>(client: &M) -> bool {
+    let provider = client.provider();
+    match provider
+        .request::<_, Value>("eth_subscribe", ["newHeads"])
+        .await
+    {
+        Ok(id) => {
+            let _ = provider.request::<_, Value>("eth_unsubscribe", [id]).await;
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Sends `method` with parameters which refer to nothing. Supporting nodes
+/// answer with an empty result or a domain error, see `DOMAIN_ERROR_CODES`.
+/// A method not found response is `Ok(false)` and other failures, including
+/// rate limits, are returned.
+pub async fn probe_method<M: Middleware>(
+    client: &M,
+    method: &str,
+    params: Value,
+) -> Result<bool, Error> {
+    match client
+        .provider()
+        .request::<_, Value>(method, params)
+        .await
+        .map_err(Error::from)
+    {
+        Ok(_) => Ok(true),
+        Err(e) if e.is_rate_limited() => Err(e),
+        Err(e) if e.is_method_not_found() => Ok(false),
+        Err(e)
+            if e.as_error_response()
+                .is_some_and(|response| DOMAIN_ERROR_CODES.contains(&response.code)) =>
+        {
+            Ok(true)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Probes `debug_traceTransaction` with the `callTracer`.
+pub async fn probe_debug<M: Middleware>(client: &M) -> Result<bool, Error> {
+    let params = json!([H256::zero(), { "tracer": "callTracer" }]);
+    probe_method(client, "debug_traceTransaction", params).await
+}
+
+/// Probes `trace_transaction`.
+pub async fn probe_trace<M: Middleware>(client: &M) -> Result<bool, Error> {
+    probe_method(client, "trace_transaction", json!([H256::zero()])).await
+}
@@ -1,10 +1,12 @@
 use {
     crate::{error::Error, named::NamedChain},
-    std::{clone::Clone, cmp},
+    serde::{de, Deserialize, Deserializer, Serialize, Serializer},
+    std::{clone::Clone, cmp, str::FromStr},
     strum_macros::Display,
 };
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RetryClientConfig {
     pub rate_limit_retries: u32,
     pub timeout_retries: u32,
@@ -21,11 +23,35 @@ impl Default for RetryClientConfig {
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, Default)]
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchClientConfig {
+    pub max_batch_size: usize,
+    pub batch_window_ms: u64,
+}
+
+impl Default for BatchClientConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 100,
+            batch_window_ms: 10,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default, Serialize, Deserialize)]
 pub struct ChainConfig {
+    #[serde(deserialize_with = "deserialize_chain_id")]
     pub chain_id: u64,
+    #[serde(default)]
     pub retry_client_config: RetryClientConfig,
+    #[serde(default)]
+    pub batch_client_config: BatchClientConfig,
+    #[serde(default)]
     pub assert_chain_id: bool,
+    /// Probe endpoint `Capabilities` when a provider is built.
+    #[serde(default)]
+    pub probe_capabilities: bool,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone, Display)]
@@ -46,6 +72,74 @@ impl From<NamedChain> for Chain {
     }
 }
 
+/// Chain id written as a number or as a chain name.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ChainKey {
+    Id(u64),
+    Name(String),
+}
+
+impl TryFrom<ChainKey> for Chain {
+    type Error = Error;
+
+    fn try_from(key: ChainKey) -> Result<Self, Self::Error> {
+        match key {
+            ChainKey::Id(chain_id) => Ok(Chain::from(chain_id)),
+            ChainKey::Name(name) => name.parse(),
+        }
+    }
+}
+
+fn deserialize_chain_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let chain = Chain::try_from(ChainKey::deserialize(deserializer)?).map_err(de::Error::custom)?;
+    Ok(chain.id())
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawChain {
+    Key(ChainKey),
+    Config(ChainConfig),
+}
+
+/// `Chain::Id` is written as the chain id and `Chain::ChainConfig` as a map.
+/// Chain names and ids are both accepted when reading.
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Chain::Id(chain_id) => serializer.serialize_u64(*chain_id),
+            Chain::ChainConfig(config) => config.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawChain::deserialize(deserializer)? {
+            RawChain::Key(key) => Chain::try_from(key).map_err(de::Error::custom),
+            RawChain::Config(config) => Ok(Chain::ChainConfig(config)),
+        }
+    }
+}
+
+/// Parses a chain id (`"10"`) or a case insensitive chain name (`"optimism"`).
+impl FromStr for Chain {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Ok(chain_id) = s.parse::<u64>() {
+            return Ok(Chain::from(chain_id));
+        }
+
+        <NamedChain as strum::IntoEnumIterator>::iter()
+            .find(|named| <&'static str>::from(*named).eq_ignore_ascii_case(s))
+            .map(Chain::from)
+            .ok_or(Error::InvalidChain(s.to_string()))
+    }
+}
+
 impl Chain {
     pub fn id(&self) -> u64 {
         match self {
@@ -97,10 +191,24 @@ impl Chain {
         }
     }
 
+    pub fn batch_client_config(&self) -> BatchClientConfig {
+        match self {
+            Chain::Id(_) => BatchClientConfig::default(),
+            Chain::ChainConfig(config) => config.batch_client_config,
+        }
+    }
+
     pub fn assert_chain_id(&self) -> bool {
         match self {
             Chain::Id(_) => true,
             Chain::ChainConfig(config) => config.assert_chain_id,
         }
     }
+
+    pub fn probe_capabilities(&self) -> bool {
+        match self {
+            Chain::Id(_) => false,
+            Chain::ChainConfig(config) => config.probe_capabilities,
+        }
+    }
 }
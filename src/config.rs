@@ -0,0 +1,232 @@
+use {
+    crate::{
+        address_book::AddressBook,
+        chain::{BatchClientConfig, Chain, ChainConfig, RetryClientConfig},
+        error::Error,
+        providers::{AbiProvider, AbiProviderTrait},
+    },
+    ethers::{providers::Middleware, types::Address},
+    serde::Deserialize,
+    std::{collections::BTreeMap, env, fs, path::Path},
+};
+
+/// RPC endpoint and contract addresses of one chain.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Endpoint {
+    pub url: String,
+    pub chain: Chain,
+    pub addresses: BTreeMap<String, Address>,
+}
+
+impl Endpoint {
+    pub fn abi_provider(&self) -> AbiProvider {
+        AbiProvider::new(self.url.clone(), self.chain)
+    }
+
+    pub async fn provider<M>(&self) -> Result<M, Error>
+    where
+        M: Middleware,
+        AbiProvider: AbiProviderTrait<M>,
+    {
+        self.abi_provider().provider().await
+    }
+}
+
+/// Chains, endpoints and address books loaded from a file. Chains are keyed by
+/// id or [`crate::named::NamedChain`] name, and `${NAME}` in a url is replaced
+/// by the environment variable `NAME`, e.g.
+///
+/// ```toml
+/// [chains.mainnet]
+/// url = "https://eth-mainnet.g.alchemy.com/v2/${ALCHEMY_API_KEY}"
+///
+/// [chains.mainnet.addresses]
+/// WETH = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+///
+/// [chains.optimism]
+/// url = "wss://optimism-rpc.publicnode.com"
+/// probe_capabilities = true
+/// retry_client_config = { rate_limit_retries = 20 }
+/// ```
+///
+/// Chains without `ChainConfig` settings behave like `Chain::Id`. Settings left
+/// out of a chain that has some take the `Chain::Id` values.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Config {
+    pub chains: BTreeMap<u64, Endpoint>,
+}
+
+/// Retry settings merged over the chain defaults, which depend on block time.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRetryClientConfig {
+    rate_limit_retries: Option<u32>,
+    timeout_retries: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawEndpoint {
+    url: String,
+    retry_client_config: Option<RawRetryClientConfig>,
+    batch_client_config: Option<BatchClientConfig>,
+    assert_chain_id: Option<bool>,
+    probe_capabilities: Option<bool>,
+    #[serde(default)]
+    addresses: BTreeMap<String, Address>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    chains: BTreeMap<String, RawEndpoint>,
+}
+
+impl RawEndpoint {
+    fn chain(&self, chain_id: u64) -> Chain {
+        let chain = Chain::from_id(chain_id);
+        let configured = self.retry_client_config.is_some()
+            || self.batch_client_config.is_some()
+            || self.assert_chain_id.is_some()
+            || self.probe_capabilities.is_some();
+        if !configured {
+            return chain;
+        }
+
+        let defaults = chain.retry_client_config();
+        let retry_client_config = match &self.retry_client_config {
+            Some(retry) => RetryClientConfig {
+                rate_limit_retries: retry
+                    .rate_limit_retries
+                    .unwrap_or(defaults.rate_limit_retries),
+                timeout_retries: retry.timeout_retries.unwrap_or(defaults.timeout_retries),
+                initial_backoff_ms: retry
+                    .initial_backoff_ms
+                    .unwrap_or(defaults.initial_backoff_ms),
+            },
+            None => defaults,
+        };
+
+        Chain::ChainConfig(ChainConfig {
+            chain_id,
+            retry_client_config,
+            batch_client_config: self
+                .batch_client_config
+                .unwrap_or(chain.batch_client_config()),
+            assert_chain_id: self.assert_chain_id.unwrap_or(chain.assert_chain_id()),
+            probe_capabilities: self
+                .probe_capabilities
+                .unwrap_or(chain.probe_capabilities()),
+        })
+    }
+}
+
+impl TryFrom<RawConfig> for Config {
+    type Error = Error;
+
+    fn try_from(raw: RawConfig) -> Result<Self, Self::Error> {
+        let mut config = Config::default();
+        for (key, endpoint) in raw.chains {
+            let chain_id = key.parse::<Chain>()?.id();
+            if config.chains.contains_key(&chain_id) {
+                return Err(Error::ConfigError(format!(
+                    "chain {chain_id} is configured more than once"
+                )));
+            }
+
+            let chain = endpoint.chain(chain_id);
+            config.chains.insert(
+                chain_id,
+                Endpoint {
+                    url: interpolate(&endpoint.url)?,
+                    chain,
+                    addresses: endpoint.addresses,
+                },
+            );
+        }
+
+        Ok(config)
+    }
+}
+
+/// Replaces every `${NAME}` in `value` with the environment variable `NAME`.
+pub fn interpolate(value: &str) -> Result<String, Error> {
+    let mut interpolated = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        interpolated.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or(Error::ConfigError(String::from("unterminated ${ in url")))?;
+        let name = &rest[start + 2..start + end];
+        let var = env::var(name)
+            .map_err(|_| Error::ConfigError(format!("environment variable {name} is not set")))?;
+        interpolated.push_str(&var);
+        rest = &rest[start + end + 1..];
+    }
+    interpolated.push_str(rest);
+
+    Ok(interpolated)
+}
+
+impl Config {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let raw: RawConfig = serde_json::from_str(json)?;
+        Self::try_from(raw)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        let raw: RawConfig = toml::from_str(toml)?;
+        Self::try_from(raw)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, Error> {
+        let raw: RawConfig = serde_yaml_ng::from_str(yaml)?;
+        Self::try_from(raw)
+    }
+
+    /// Loads a config, choosing the format from the file extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            Some("yaml") | Some("yml") => Self::from_yaml(&contents),
+            _ => Err(Error::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+
+    pub fn endpoint(&self, chain: Chain) -> Result<&Endpoint, Error> {
+        self.chains.get(&chain.id()).ok_or(Error::UnknownChain {
+            chain_id: chain.id(),
+        })
+    }
+
+    pub fn chains(&self) -> Vec<Chain> {
+        self.chains
+            .values()
+            .map(|endpoint| endpoint.chain)
+            .collect()
+    }
+
+    pub async fn provider<M>(&self, chain: Chain) -> Result<M, Error>
+    where
+        M: Middleware,
+        AbiProvider: AbiProviderTrait<M>,
+    {
+        self.endpoint(chain)?.provider().await
+    }
+
+    pub fn address_book(&self) -> AddressBook {
+        let mut book = AddressBook::new();
+        for (chain_id, endpoint) in &self.chains {
+            book.chains.insert(*chain_id, endpoint.addresses.clone());
+        }
+
+        book
+    }
+}
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        capabilities::Capabilities,
+        chain::Chain,
+        error::Error,
+        providers::{AbiProvider, AbiProviderTrait},
+    },
+    dashmap::DashMap,
+    ethers::{abi::Abi, providers::Middleware, types::Address},
+    std::{
+        any::{Any, TypeId},
+        sync::Arc,
+    },
+};
+
+/// Implemented by `abirpc!` for the abigen generated contract type.
+pub trait AbiContract<M>: Clone + Send + Sync + 'static {
+    /// Name of the contract binding passed to `abirpc!`.
+    const NAME: &'static str;
+
+    fn from_address(address: Address, client: Arc<M>) -> Self;
+
+    fn contract_address(&self) -> Address;
+
+    fn contract_abi(&self) -> &Abi;
+}
+
+#[derive(Debug)]
+struct AnyContract {
+    name: &'static str,
+    contract: Box<dyn Any + Send + Sync>,
+}
+
+/// Registry holding instances of several contract types behind one provider
+/// and chain. Entries are keyed by address and contract type, so the same
+/// address may be registered under more than one ABI.
+#[derive(Debug)]
+pub struct ContractRegistry<M> {
+    pub chain: Option<Chain>,
+    provider: Arc<M>,
+    capabilities: Option<Capabilities>,
+    registry: DashMap<(Address, TypeId), AnyContract>,
+}
+
+impl<M> ContractRegistry<M>
+where
+    M: Middleware + 'static,
+{
+    pub fn new(provider: M, chain: Chain) -> Self {
+        Self::_new(provider, Some(chain))
+    }
+
+    pub fn mock(provider: M) -> Self {
+        Self::_new(provider, None)
+    }
+
+    fn _new(provider: M, chain: Option<Chain>) -> Self {
+        Self {
+            chain,
+            provider: Arc::new(provider),
+            capabilities: None,
+            registry: DashMap::new(),
+        }
+    }
+
+    pub fn provider(&self) -> Arc<M> {
+        Arc::clone(&self.provider)
+    }
+
+    /// Capabilities probed by `connect`, see `ChainConfig::probe_capabilities`.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.capabilities
+    }
+
+    pub fn register<T: AbiContract<M>>(&self, address: Address) -> T {
+        let entry = self
+            .registry
+            .entry((address, TypeId::of::<T>()))
+            .or_insert_with(|| AnyContract {
+                name: T::NAME,
+                contract: Box::new(T::from_address(address, self.provider())),
+            });
+
+        entry
+            .contract
+            .downcast_ref::<T>()
+            .expect("entry is keyed by its TypeId")
+            .clone()
+    }
+
+    pub fn get<T: AbiContract<M>>(&self, address: Address) -> Option<T> {
+        self.registry
+            .get(&(address, TypeId::of::<T>()))
+            .and_then(|entry| entry.contract.downcast_ref::<T>().cloned())
+    }
+
+    pub fn contains<T: AbiContract<M>>(&self, address: Address) -> bool {
+        self.registry.contains_key(&(address, TypeId::of::<T>()))
+    }
+
+    pub fn unregister<T: AbiContract<M>>(&self, address: Address) -> Option<T> {
+        self.registry
+            .remove(&(address, TypeId::of::<T>()))
+            .and_then(|(_, entry)| entry.contract.downcast::<T>().ok())
+            .map(|contract| *contract)
+    }
+
+    /// All instances of type `T`.
+    pub fn entries<T: AbiContract<M>>(&self) -> Vec<(Address, T)> {
+        self.registry
+            .iter()
+            .filter(|entry| entry.key().1 == TypeId::of::<T>())
+            .filter_map(|entry| {
+                let contract = entry.contract.downcast_ref::<T>()?.clone();
+                Some((entry.key().0, contract))
+            })
+            .collect()
+    }
+
+    /// Registered addresses, each listed once.
+    pub fn addresses(&self) -> Vec<Address> {
+        let mut addresses: Vec<Address> = self.registry.iter().map(|entry| entry.key().0).collect();
+        addresses.sort();
+        addresses.dedup();
+
+        addresses
+    }
+
+    /// Names of the contract types registered at `address`.
+    pub fn names(&self, address: Address) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self
+            .registry
+            .iter()
+            .filter(|entry| entry.key().0 == address)
+            .map(|entry| entry.name)
+            .collect();
+        names.sort();
+
+        names
+    }
+
+    pub fn len(&self) -> usize {
+        self.registry.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registry.is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.registry.clear()
+    }
+}
+
+impl<M> ContractRegistry<M>
+where
+    M: Middleware + 'static,
+    AbiProvider: AbiProviderTrait<M>,
+{
+    pub async fn connect(url: String, chain: Chain) -> Result<Self, Error> {
+        let abi_provider = AbiProvider::new(url, chain);
+        let provider = abi_provider.provider().await?;
+
+        Ok(Self {
+            capabilities: abi_provider.capabilities(),
+            ..Self::new(provider, chain)
+        })
+    }
+}
@@ -0,0 +1,247 @@
+use {
+    crate::{
+        dynamic::{decode_log, param_name, token_to_json, DecodedEvent},
+        error::Error,
+        verify::signature,
+    },
+    dashmap::DashMap,
+    ethers::{
+        abi::{Abi, Function},
+        contract::BaseContract,
+        providers::Middleware,
+        types::{Address, Bytes, Log, H256},
+        utils::hex,
+    },
+    serde::Serialize,
+    serde_json::{Map, Value},
+    std::{
+        collections::BTreeSet,
+        fmt,
+        sync::{Arc, PoisonError, RwLock},
+    },
+};
+
+/// Function call decoded against a registered ABI.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DecodedCall {
+    /// Name of the contract binding the address is registered under.
+    pub contract: String,
+    pub address: Address,
+    pub function: String,
+    pub signature: String,
+    pub args: Map<String, Value>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DecodedLog {
+    pub contract: String,
+    #[serde(flatten)]
+    pub event: DecodedEvent,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DecodedTransaction {
+    pub hash: H256,
+    pub from: Address,
+    pub to: Option<Address>,
+    /// `None` for contract creations, transfers without calldata and
+    /// unregistered recipients.
+    pub call: Option<DecodedCall>,
+    /// Calldata sent to a registered address whose selector matches no
+    /// function in its ABI, e.g. a fallback or a call forwarded by a proxy.
+    pub undecoded_call: Option<Bytes>,
+    /// Logs emitted by registered addresses, in receipt order.
+    pub logs: Vec<DecodedLog>,
+    /// Logs emitted by registered addresses which match no event in their ABI.
+    pub undecoded: Vec<Log>,
+}
+
+/// Registry resolved by `TxDecoder` on every lookup.
+pub(crate) trait DecoderSource: fmt::Debug + Send + Sync {
+    /// Identifies the underlying storage, so a registry is added once.
+    fn id(&self) -> usize;
+
+    /// Contract name and decoding contract of the entry at `address`.
+    fn get(&self, address: Address) -> Option<(String, Arc<BaseContract>)>;
+
+    fn addresses(&self) -> Vec<Address>;
+}
+
+/// Decodes calldata and logs of registered addresses. Registries added with
+/// `add_to_decoder` are resolved on every lookup, and addresses inserted
+/// directly take precedence over them.
+#[derive(Debug, Default)]
+pub struct TxDecoder {
+    contracts: DashMap<Address, (String, Arc<BaseContract>)>,
+    sources: RwLock<Vec<Arc<dyn DecoderSource>>>,
+}
+
+impl TxDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, address: Address, contract: impl Into<String>, abi: Arc<Abi>) {
+        let base = BaseContract::from(Arc::unwrap_or_clone(abi));
+        self.contracts
+            .insert(address, (contract.into(), Arc::new(base)));
+    }
+
+    /// Removes an address added with `insert`. Registry entries are removed by
+    /// unregistering them.
+    pub fn remove(&self, address: Address) -> Option<String> {
+        self.contracts
+            .remove(&address)
+            .map(|(_, (contract, _))| contract)
+    }
+
+    pub fn contains(&self, address: Address) -> bool {
+        self.get(address).is_some()
+    }
+
+    /// Name of the contract binding `address` is registered under.
+    pub fn contract(&self, address: Address) -> Option<String> {
+        self.get(address).map(|(contract, _)| contract)
+    }
+
+    /// Number of addresses which can be decoded.
+    pub fn len(&self) -> usize {
+        let mut addresses: BTreeSet<Address> =
+            self.contracts.iter().map(|entry| *entry.key()).collect();
+        for source in self.sources().iter() {
+            addresses.extend(source.addresses());
+        }
+
+        addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn add_source(&self, source: Arc<dyn DecoderSource>) {
+        let mut sources = self.sources.write().unwrap_or_else(PoisonError::into_inner);
+        if !sources.iter().any(|existing| existing.id() == source.id()) {
+            sources.push(source);
+        }
+    }
+
+    fn sources(&self) -> Vec<Arc<dyn DecoderSource>> {
+        self.sources
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn get(&self, address: Address) -> Option<(String, Arc<BaseContract>)> {
+        if let Some(entry) = self.contracts.get(&address) {
+            return Some((entry.0.clone(), Arc::clone(&entry.1)));
+        }
+
+        self.sources().iter().find_map(|source| source.get(address))
+    }
+
+    /// Decodes calldata sent to `address`. Returns `None` for unregistered
+    /// addresses and an error for selectors missing from the ABI.
+    pub fn decode_calldata(
+        &self,
+        address: Address,
+        data: &[u8],
+    ) -> Result<Option<DecodedCall>, Error> {
+        let Some((contract, base)) = self.get(address) else {
+            return Ok(None);
+        };
+        if data.len() < 4 {
+            return Err(Error::UnknownFunction(format!("0x{}", hex::encode(data))));
+        }
+
+        let function = selector_function(&base, data)
+            .ok_or_else(|| Error::UnknownFunction(format!("0x{}", hex::encode(&data[..4]))))?;
+        let args = function
+            .decode_input(&data[4..])?
+            .iter()
+            .zip(&function.inputs)
+            .enumerate()
+            .map(|(i, (token, param))| (param_name(&param.name, i), token_to_json(token)))
+            .collect();
+
+        Ok(Some(DecodedCall {
+            contract,
+            address,
+            function: function.name.clone(),
+            signature: signature(function),
+            args,
+        }))
+    }
+
+    /// Decodes a log emitted by a registered address. Returns `None` for
+    /// unregistered addresses and an error for unknown events.
+    pub fn decode_log(&self, log: &Log) -> Result<Option<DecodedLog>, Error> {
+        let Some((contract, base)) = self.get(log.address) else {
+            return Ok(None);
+        };
+
+        Ok(Some(DecodedLog {
+            contract,
+            event: decode_log(base.abi(), log)?,
+        }))
+    }
+
+    /// Fetches a transaction and its receipt and decodes the call and every log
+    /// emitted by a registered address. Pending transactions have no logs.
+    pub async fn decode_transaction<M>(
+        &self,
+        client: &M,
+        hash: H256,
+    ) -> Result<DecodedTransaction, Error>
+    where
+        M: Middleware,
+    {
+        let tx = client
+            .get_transaction(hash)
+            .await
+            .map_err(Error::from_middleware_error)?
+            .ok_or(Error::TransactionNotFound(hash))?;
+        let receipt = client
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(Error::from_middleware_error)?;
+
+        let (call, undecoded_call) = match tx.to {
+            Some(to) if !tx.input.is_empty() => match self.decode_calldata(to, &tx.input) {
+                Ok(call) => (call, None),
+                Err(Error::UnknownFunction(_)) => (None, Some(tx.input.clone())),
+                Err(e) => return Err(e),
+            },
+            _ => (None, None),
+        };
+
+        let mut logs = vec![];
+        let mut undecoded = vec![];
+        for log in receipt.map(|receipt| receipt.logs).unwrap_or_default() {
+            match self.decode_log(&log) {
+                Ok(Some(decoded)) => logs.push(decoded),
+                Ok(None) => {}
+                Err(_) => undecoded.push(log),
+            }
+        }
+
+        Ok(DecodedTransaction {
+            hash,
+            from: tx.from,
+            to: tx.to,
+            call,
+            undecoded_call,
+            logs,
+            undecoded,
+        })
+    }
+}
+
+/// Function called by `data`, looked up in the contract's selector map.
+pub(crate) fn selector_function<'a>(base: &'a BaseContract, data: &[u8]) -> Option<&'a Function> {
+    let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let (name, index) = base.methods.get(&selector)?;
+
+    base.abi().functions.get(name)?.get(*index)
+}
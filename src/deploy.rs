@@ -0,0 +1,123 @@
+use {
+    crate::error::Error,
+    ethers::{
+        abi::{Abi, Tokenize},
+        contract::ContractFactory,
+        providers::Middleware,
+        types::{Address, Bytes, Eip1559TransactionRequest, TransactionReceipt, H160, H256, U64},
+        utils::get_create2_address,
+    },
+    std::sync::Arc,
+};
+
+/// Deterministic deployment proxy available at the same address on most EVM
+/// chains. It deploys `init_code` with CREATE2 when called with
+/// `salt ++ init_code`.
+pub const CREATE2_DEPLOYER: Address = H160([
+    0x4e, 0x59, 0xb4, 0x48, 0x47, 0xb3, 0x79, 0x57, 0x85, 0x88, 0x92, 0x0c, 0xa7, 0x8f, 0xbf, 0x26,
+    0xc0, 0xb4, 0x95, 0x6c,
+]);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Deployment {
+    pub address: Address,
+    /// `None` when a CREATE2 deployment found the contract already deployed.
+    pub receipt: Option<TransactionReceipt>,
+}
+
+impl Deployment {
+    pub fn block_number(&self) -> Option<u64> {
+        self.receipt
+            .as_ref()
+            .and_then(|receipt| receipt.block_number)
+            .map(|block| block.as_u64())
+    }
+}
+
+/// Constructor arguments are ABI encoded and appended to `bytecode`.
+pub fn init_code<T: Tokenize>(abi: &Abi, bytecode: Bytes, args: T) -> Result<Bytes, Error> {
+    let tokens = args.into_tokens();
+    let init_code = match (abi.constructor(), tokens.is_empty()) {
+        (None, false) => return Err(Error::MissingConstructor),
+        (None, true) => bytecode,
+        (Some(constructor), _) => constructor.encode_input(bytecode.to_vec(), &tokens)?.into(),
+    };
+
+    Ok(init_code)
+}
+
+pub fn create2_address(init_code: &Bytes, salt: H256) -> Address {
+    get_create2_address(CREATE2_DEPLOYER, salt, init_code)
+}
+
+/// Sends a CREATE deployment through `client` and waits for the receipt.
+pub async fn deploy<M, T>(
+    client: Arc<M>,
+    abi: Abi,
+    bytecode: Bytes,
+    args: T,
+) -> Result<Deployment, Error>
+where
+    M: Middleware,
+    T: Tokenize,
+{
+    let factory = ContractFactory::new(abi, bytecode, client);
+    let (contract, receipt) = factory.deploy(args)?.send_with_receipt().await?;
+    // A reverted creation still reports the address it would have had
+    if receipt.status != Some(U64::from(1)) {
+        return Err(Error::DeploymentFailed {
+            tx_hash: receipt.transaction_hash,
+        });
+    }
+
+    Ok(Deployment {
+        address: contract.address(),
+        receipt: Some(receipt),
+    })
+}
+
+/// Deploys `init_code` through [`CREATE2_DEPLOYER`]. Deployments whose address
+/// already holds code are not sent again.
+pub async fn deploy_create2<M>(
+    client: &M,
+    init_code: Bytes,
+    salt: H256,
+) -> Result<Deployment, Error>
+where
+    M: Middleware,
+{
+    let address = create2_address(&init_code, salt);
+    let code = client
+        .get_code(address, None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    if !code.is_empty() {
+        return Ok(Deployment {
+            address,
+            receipt: None,
+        });
+    }
+
+    let mut data = salt.as_bytes().to_vec();
+    data.extend_from_slice(&init_code);
+    let tx = Eip1559TransactionRequest::new()
+        .to(CREATE2_DEPLOYER)
+        .data(Bytes::from(data));
+
+    let pending = client
+        .send_transaction(tx, None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    let tx_hash = pending.tx_hash();
+    let receipt = pending.await?;
+    match receipt {
+        Some(receipt) if receipt.status == Some(U64::from(1)) => Ok(Deployment {
+            address,
+            receipt: Some(receipt),
+        }),
+        Some(receipt) => Err(Error::DeploymentFailed {
+            tx_hash: receipt.transaction_hash,
+        }),
+        None => Err(Error::TransactionDropped { tx_hash }),
+    }
+}
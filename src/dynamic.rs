@@ -0,0 +1,573 @@
+use {
+    crate::{
+        capabilities::Capabilities,
+        chain::Chain,
+        decode::TxDecoder,
+        error::Error,
+        registry::{AbiRegistry, RegistryConfig, RegistryMetrics},
+        snapshot::EntryMetadata,
+        verify::{event_signature, signature},
+    },
+    ethers::{
+        abi::{
+            token::{LenientTokenizer, Tokenizer},
+            Abi, Event, Function, Param, ParamType, RawLog, Token,
+        },
+        contract::BaseContract,
+        providers::Middleware,
+        types::{
+            transaction::eip2718::TypedTransaction, Address, Bytes, Filter, Log, ParseI256Error,
+            TransactionReceipt, TransactionRequest, H256, I256, U256, U64,
+        },
+        utils::to_checksum,
+    },
+    serde::Serialize,
+    serde_json::{Map, Value},
+    std::sync::Arc,
+};
+
+/// Contract with an ABI loaded at runtime. Functions are called by name, or by
+/// signature when overloaded, with JSON or `Token` arguments.
+#[derive(Debug)]
+pub struct DynamicContract<M> {
+    address: Address,
+    base: Arc<BaseContract>,
+    client: Arc<M>,
+}
+
+impl<M> Clone for DynamicContract<M> {
+    fn clone(&self) -> Self {
+        Self {
+            address: self.address,
+            base: Arc::clone(&self.base),
+            client: Arc::clone(&self.client),
+        }
+    }
+}
+
+/// Event log decoded against a runtime ABI.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub address: Address,
+    pub params: Map<String, Value>,
+    pub block_number: Option<U64>,
+    pub transaction_hash: Option<H256>,
+    pub log_index: Option<U256>,
+}
+
+impl<M> DynamicContract<M>
+where
+    M: Middleware,
+{
+    pub fn new(address: Address, abi: Abi, client: Arc<M>) -> Self {
+        Self {
+            address,
+            base: Arc::new(BaseContract::from(abi)),
+            client,
+        }
+    }
+
+    pub fn from_json(address: Address, abi: &str, client: Arc<M>) -> Result<Self, Error> {
+        Ok(Self::new(address, serde_json::from_str(abi)?, client))
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn abi(&self) -> &Abi {
+        self.base.abi()
+    }
+
+    pub fn client(&self) -> Arc<M> {
+        Arc::clone(&self.client)
+    }
+
+    /// Looks up a function by name or by signature, e.g. `transfer(address,uint256)`.
+    /// Overloaded names are resolved by argument count when given.
+    pub fn function(&self, name: &str, arg_count: Option<usize>) -> Result<&Function, Error> {
+        if name.contains('(') {
+            return self
+                .abi()
+                .functions()
+                .find(|function| signature(function) == name)
+                .ok_or_else(|| Error::UnknownFunction(name.to_string()));
+        }
+
+        let functions = self
+            .abi()
+            .functions
+            .get(name)
+            .ok_or_else(|| Error::UnknownFunction(name.to_string()))?;
+        let candidates: Vec<&Function> = functions
+            .iter()
+            .filter(|function| {
+                functions.len() == 1 || arg_count.is_none_or(|count| function.inputs.len() == count)
+            })
+            .collect();
+
+        match candidates.as_slice() {
+            [function] => Ok(function),
+            [] => Err(Error::NoMatchingOverload {
+                name: name.to_string(),
+                arg_count: arg_count.unwrap_or_default(),
+                candidates: functions.iter().map(signature).collect(),
+            }),
+            candidates => Err(Error::AmbiguousOverload {
+                name: name.to_string(),
+                candidates: candidates
+                    .iter()
+                    .map(|function| signature(function))
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Looks up an event by name or by signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn event(&self, name: &str) -> Result<&Event, Error> {
+        if name.contains('(') {
+            return self
+                .abi()
+                .events()
+                .find(|event| event_signature(event) == name)
+                .ok_or_else(|| Error::UnknownEvent(name.to_string()));
+        }
+
+        match self.abi().events.get(name).map(Vec::as_slice) {
+            Some([event]) => Ok(event),
+            Some(events) if !events.is_empty() => Err(Error::AmbiguousOverload {
+                name: name.to_string(),
+                candidates: events.iter().map(event_signature).collect(),
+            }),
+            _ => Err(Error::UnknownEvent(name.to_string())),
+        }
+    }
+
+    pub fn encode(&self, name: &str, args: &[Value]) -> Result<Bytes, Error> {
+        let function = self.function(name, Some(args.len()))?;
+        let tokens = tokenize_params(&function.inputs, args)?;
+
+        Ok(Bytes::from(function.encode_input(&tokens)?))
+    }
+
+    pub fn encode_tokens(&self, name: &str, args: &[Token]) -> Result<Bytes, Error> {
+        let function = self.function(name, Some(args.len()))?;
+
+        Ok(Bytes::from(function.encode_input(args)?))
+    }
+
+    /// Calls a function and decodes its outputs to JSON, see `outputs_to_json`.
+    pub async fn call(&self, name: &str, args: &[Value]) -> Result<Value, Error> {
+        let function = self.function(name, Some(args.len()))?;
+        let tokens = tokenize_params(&function.inputs, args)?;
+        let outputs = self
+            .call_tokens(signature(function).as_str(), &tokens)
+            .await?;
+
+        Ok(outputs_to_json(&function.outputs, outputs))
+    }
+
+    pub async fn call_tokens(&self, name: &str, args: &[Token]) -> Result<Vec<Token>, Error> {
+        let function = self.function(name, Some(args.len()))?;
+        let tx: TypedTransaction = TransactionRequest::new()
+            .to(self.address)
+            .data(function.encode_input(args)?)
+            .into();
+
+        let output = self
+            .client
+            .call(&tx, None)
+            .await
+            .map_err(Error::from_middleware_error)?;
+
+        Ok(function.decode_output(&output)?)
+    }
+
+    /// Unsigned transaction calling `name`, for callers which set value, gas or
+    /// nonce before sending.
+    pub fn transaction(&self, name: &str, args: &[Value]) -> Result<TypedTransaction, Error> {
+        let tx = TransactionRequest::new()
+            .to(self.address)
+            .data(self.encode(name, args)?);
+
+        Ok(tx.into())
+    }
+
+    /// Sends a transaction calling `name` and waits for its receipt.
+    pub async fn send(
+        &self,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Option<TransactionReceipt>, Error> {
+        let tx = self.transaction(name, args)?;
+        let receipt = self
+            .client
+            .send_transaction(tx, None)
+            .await
+            .map_err(Error::from_middleware_error)?
+            .await?;
+
+        Ok(receipt)
+    }
+
+    /// Decodes a log emitted by this contract's ABI. Anonymous events are not
+    /// matched.
+    pub fn decode_log(&self, log: &Log) -> Result<DecodedEvent, Error> {
+        decode_log(self.abi(), log)
+    }
+
+    /// Fetches and decodes `name` events emitted by this contract. The filter's
+    /// block range and indexed topics are kept.
+    pub async fn events(&self, name: &str, filter: Filter) -> Result<Vec<DecodedEvent>, Error> {
+        let event = self.event(name)?;
+        let filter = filter.address(self.address).topic0(event.signature());
+
+        let logs = self
+            .client
+            .get_logs(&filter)
+            .await
+            .map_err(Error::from_middleware_error)?;
+
+        logs.iter().map(|log| self.decode_log(log)).collect()
+    }
+}
+
+pub(crate) fn decode_log(abi: &Abi, log: &Log) -> Result<DecodedEvent, Error> {
+    let topic = log
+        .topics
+        .first()
+        .ok_or_else(|| Error::UnknownEvent(String::from("anonymous")))?;
+    let event = abi
+        .events()
+        .find(|event| !event.anonymous && event.signature() == *topic)
+        .ok_or_else(|| Error::UnknownEvent(format!("{topic:?}")))?;
+
+    let raw = RawLog {
+        topics: log.topics.clone(),
+        data: log.data.to_vec(),
+    };
+    let params = event
+        .parse_log(raw)?
+        .params
+        .into_iter()
+        .enumerate()
+        .map(|(i, param)| (param_name(&param.name, i), token_to_json(&param.value)))
+        .collect();
+
+    Ok(DecodedEvent {
+        name: event.name.clone(),
+        address: log.address,
+        params,
+        block_number: log.block_number,
+        transaction_hash: log.transaction_hash,
+        log_index: log.log_index,
+    })
+}
+
+pub(crate) fn param_name(name: &str, index: usize) -> String {
+    match name.is_empty() {
+        true => index.to_string(),
+        false => name.to_string(),
+    }
+}
+
+fn tokenize_params(params: &[Param], args: &[Value]) -> Result<Vec<Token>, Error> {
+    if params.len() != args.len() {
+        return Err(Error::InvalidArgument(format!(
+            "expected {} arguments, got {}",
+            params.len(),
+            args.len()
+        )));
+    }
+
+    params
+        .iter()
+        .zip(args)
+        .enumerate()
+        .map(|(i, (param, arg))| {
+            json_to_token(&param.kind, arg).map_err(|e| match e {
+                Error::InvalidArgument(message) => {
+                    Error::InvalidArgument(format!("{}: {message}", param_name(&param.name, i)))
+                }
+                e => e,
+            })
+        })
+        .collect()
+}
+
+/// Converts a JSON value to a token of type `kind`. Integers are accepted as
+/// numbers, decimal or `0x` strings, or with units such as `"1.5 ether"`.
+/// Signed integers may be negative in either base, e.g. `"-0x80"`, and all
+/// integers must fit the parameter width. Arrays and tuples are JSON arrays,
+/// bytes are hex strings.
+pub fn json_to_token(kind: &ParamType, value: &Value) -> Result<Token, Error> {
+    let invalid = || Error::InvalidArgument(format!("expected {kind}, got {value}"));
+    let out_of_range = || Error::InvalidArgument(format!("{value} is out of range for {kind}"));
+    let text = match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(value) => value.to_string(),
+        _ => String::new(),
+    };
+
+    let token = match kind {
+        ParamType::Address => Token::Address(text.parse().map_err(|_| invalid())?),
+        ParamType::Bool => match value {
+            Value::Bool(value) => Token::Bool(*value),
+            _ => LenientTokenizer::tokenize(kind, &text).map_err(|_| invalid())?,
+        },
+        ParamType::String => match value {
+            Value::String(text) => Token::String(text.clone()),
+            _ => return Err(invalid()),
+        },
+        ParamType::Bytes => Token::Bytes(text.parse::<Bytes>().map_err(|_| invalid())?.to_vec()),
+        ParamType::FixedBytes(len) => {
+            let bytes = text.parse::<Bytes>().map_err(|_| invalid())?;
+            if bytes.len() != *len {
+                return Err(invalid());
+            }
+            Token::FixedBytes(bytes.to_vec())
+        }
+        ParamType::Uint(bits) => {
+            let uint = match text.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(&text).ok(),
+            };
+            let uint = match uint {
+                Some(uint) => uint,
+                None => LenientTokenizer::tokenize_uint(&text)
+                    .map_err(|_| invalid())?
+                    .into(),
+            };
+            if uint.bits() > *bits {
+                return Err(out_of_range());
+            }
+            Token::Uint(uint)
+        }
+        ParamType::Int(bits) => {
+            let (sign, abs) = match text.strip_prefix('-') {
+                Some(abs) => ("-", abs),
+                None => ("", text.as_str()),
+            };
+            let int = match abs.strip_prefix("0x") {
+                Some(hex) => I256::from_hex_str(&format!("{sign}{hex}")),
+                None => I256::from_dec_str(&text),
+            };
+            let int = int.map_err(|e| match e {
+                ParseI256Error::IntegerOverflow => out_of_range(),
+                _ => invalid(),
+            })?;
+            if int.bits() as usize > *bits {
+                return Err(out_of_range());
+            }
+            Token::Int(int.into_raw())
+        }
+        ParamType::Array(inner) => match value {
+            Value::Array(values) => Token::Array(
+                values
+                    .iter()
+                    .map(|value| json_to_token(inner, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(invalid()),
+        },
+        ParamType::FixedArray(inner, len) => match value {
+            Value::Array(values) if values.len() == *len => Token::FixedArray(
+                values
+                    .iter()
+                    .map(|value| json_to_token(inner, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(invalid()),
+        },
+        ParamType::Tuple(kinds) => match value {
+            Value::Array(values) if values.len() == kinds.len() => Token::Tuple(
+                kinds
+                    .iter()
+                    .zip(values)
+                    .map(|(kind, value)| json_to_token(kind, value))
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => return Err(invalid()),
+        },
+    };
+
+    Ok(token)
+}
+
+/// Converts a token to JSON. Integers are decimal strings to avoid precision
+/// loss, addresses are checksummed and bytes are `0x` hex strings.
+pub fn token_to_json(token: &Token) -> Value {
+    match token {
+        Token::Address(address) => Value::String(to_checksum(address, None)),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+            Value::String(Bytes::from(bytes.clone()).to_string())
+        }
+        Token::Int(int) => Value::String(I256::from_raw(*int).to_string()),
+        Token::Uint(uint) => Value::String(uint.to_string()),
+        Token::Bool(value) => Value::Bool(*value),
+        Token::String(text) => Value::String(text.clone()),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            Value::Array(tokens.iter().map(token_to_json).collect())
+        }
+    }
+}
+
+/// Converts function outputs to JSON. A single output is returned as is, named
+/// outputs as an object and unnamed outputs as an array.
+pub fn outputs_to_json(params: &[Param], tokens: Vec<Token>) -> Value {
+    if tokens.len() == 1 {
+        return token_to_json(&tokens[0]);
+    }
+    if !params.is_empty() && params.iter().all(|param| !param.name.is_empty()) {
+        let outputs = params
+            .iter()
+            .zip(&tokens)
+            .map(|(param, token)| (param.name.clone(), token_to_json(token)))
+            .collect();
+        return Value::Object(outputs);
+    }
+
+    Value::Array(tokens.iter().map(token_to_json).collect())
+}
+
+/// Registry of runtime ABI contracts sharing the provider setup of `abirpc!`
+/// registries.
+#[derive(Debug)]
+pub struct DynamicRegistry<M>(AbiRegistry<DynamicContract<M>>)
+where
+    M: Middleware;
+
+impl<M> DynamicRegistry<M>
+where
+    M: Middleware,
+{
+    pub fn new(url: String, chain: Chain) -> Self {
+        Self(AbiRegistry::new(url, chain))
+    }
+
+    pub fn mock() -> Self {
+        Self(AbiRegistry::mock())
+    }
+
+    pub fn with_config(self, config: RegistryConfig) -> Self {
+        Self(self.0.with_config(config))
+    }
+
+    pub fn config(&self) -> RegistryConfig {
+        self.0.config
+    }
+
+    pub fn metrics(&self) -> RegistryMetrics {
+        self.0.metrics()
+    }
+
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.0.capabilities()
+    }
+
+    pub fn chain(&self) -> Option<Chain> {
+        self.0.chain
+    }
+
+    /// Registers `address` with `abi`, replacing any previously registered ABI.
+    pub fn register(&self, provider: M, address: Address, abi: Abi) -> DynamicContract<M> {
+        let instance = DynamicContract::new(address, abi, Arc::new(provider));
+        self.0.add_entry(address, instance.clone());
+
+        instance
+    }
+
+    pub fn register_json(
+        &self,
+        provider: M,
+        address: Address,
+        abi: &str,
+    ) -> Result<DynamicContract<M>, Error> {
+        Ok(self.register(provider, address, serde_json::from_str(abi)?))
+    }
+
+    pub fn register_as(
+        &self,
+        provider: M,
+        alias: impl Into<String>,
+        address: Address,
+        abi: Abi,
+    ) -> DynamicContract<M> {
+        let instance = self.register(provider, address, abi);
+        self.0.add_alias(alias, address);
+
+        instance
+    }
+
+    pub fn alias(&self, alias: impl Into<String>, address: Address) -> Option<Address> {
+        self.0.add_alias(alias, address)
+    }
+
+    pub fn unalias(&self, alias: &str) -> Option<Address> {
+        self.0.remove_alias(alias)
+    }
+
+    pub fn resolve(&self, alias: &str) -> Option<Address> {
+        self.0.resolve_alias(alias)
+    }
+
+    pub fn get_by_alias(&self, alias: &str) -> Option<DynamicContract<M>> {
+        self.0.get_entry_by_alias(alias)
+    }
+
+    pub fn unregister(&self, address: Address) -> Option<DynamicContract<M>> {
+        self.0.remove_entry(address)
+    }
+
+    pub fn get(&self, address: Address) -> Option<DynamicContract<M>> {
+        self.0.get_entry(address)
+    }
+
+    pub fn contains(&self, address: Address) -> bool {
+        self.0.entry_exists(address)
+    }
+
+    pub fn addresses(&self) -> Vec<Address> {
+        self.0.addresses()
+    }
+
+    pub fn entries(&self) -> Vec<(Address, DynamicContract<M>)> {
+        self.0.entries()
+    }
+
+    /// Decodes calls and logs of registered contracts under their own ABI,
+    /// including contracts registered after this call.
+    pub fn add_to_decoder(&self, decoder: &TxDecoder)
+    where
+        M: 'static,
+    {
+        self.0
+            .add_to_decoder(decoder, "DynamicContract", |instance| {
+                Arc::clone(&instance.base)
+            })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.0.clear()
+    }
+
+    pub fn set_metadata(&self, address: Address, metadata: EntryMetadata) {
+        self.0.set_metadata(address, metadata)
+    }
+
+    pub fn metadata(&self, address: Address) -> Option<EntryMetadata> {
+        self.0.get_metadata(address)
+    }
+}
+
+crate::__abirpc_transports!(crate, DynamicRegistry);
@@ -1,28 +1,292 @@
-use thiserror::Error;
+use {
+    crate::{pinned::ArchiveError, providers::Transport, revert::Revert},
+    ethers::{
+        abi::InvalidOutputType,
+        contract::{ContractError, MulticallError},
+        providers::{JsonRpcError, Middleware, MiddlewareError, ProviderError, RpcError},
+        signers::WalletError,
+        types::{Address, BlockId, H256, U256},
+    },
+    std::path::PathBuf,
+    thiserror::Error,
+};
+
+// JSON-RPC error codes and messages used by providers to signal rate limiting
+const RATE_LIMIT_CODES: [i64; 2] = [429, -32005];
+const RATE_LIMIT_MESSAGES: [&str; 4] = [
+    "rate limit",
+    "too many requests",
+    "request rate exceeded",
+    "compute units per second",
+];
+// Method not found and method not supported
+const METHOD_NOT_FOUND_CODES: [i64; 2] = [-32601, -32004];
+// Messages used for unknown or disabled methods by clients which do not use -32601
+const METHOD_NOT_FOUND_MESSAGES: [&str; 4] = [
+    "not found",
+    "not supported",
+    "does not exist",
+    "not available",
+];
 
 #[derive(Error, Debug)]
 pub enum Error {
+    #[error("Snapshot abi ({actual}) does not match registry abi ({expected})")]
+    AbiMismatch { expected: String, actual: String },
     #[error("Abi error: {0}")]
     AbiError(#[from] ethers::abi::Error),
+    #[error("Abigen error: {0}")]
+    AbigenError(String),
     #[error("Hex error: {0}")]
     AddressParseError(#[from] rustc_hex::FromHexError),
-    #[error("ChainId error: {0}")]
-    ChainIdError(String),
-    #[error("ChainId error: {0}")]
-    NamedChainError(#[from] num_enum::TryFromPrimitiveError<crate::named::NamedChain>),
+    #[error("{name} is overloaded, use one of {}", candidates.join(", "))]
+    AmbiguousOverload {
+        name: String,
+        candidates: Vec<String>,
+    },
+    #[error("State at block {block:?} is unavailable, an archive node is required: {message}")]
+    ArchiveNodeRequired { block: BlockId, message: String },
+    #[error("Configured chain_id ({expected}) does not match chain ({actual})")]
+    ChainIdMismatch { expected: u64, actual: U256 },
+    #[error("{url} failed checks: {}", checks.join(", "))]
+    ChecksFailed { url: String, checks: Vec<String> },
+    #[error("Config error: {0}")]
+    ConfigError(String),
+    #[error("Deployment transaction {tx_hash:?} reverted")]
+    DeploymentFailed { tx_hash: H256 },
+    #[error("Detokenize error: {0}")]
+    DetokenizeError(#[from] InvalidOutputType),
+    #[error("Contract {name} is defined by both {} and {}", first.display(), second.display())]
+    DuplicateContract {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+    #[error("Module {module} is generated by both {} and {}", first.display(), second.display())]
+    DuplicateModule {
+        module: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
     #[error("Error: {0}")]
     Error(String),
+    #[error("Contract at {address:?} does not support interface {interface_id}")]
+    InterfaceNotSupported {
+        address: Address,
+        interface_id: String,
+    },
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("{} is not a JSON ABI or artifact", path.display())]
+    InvalidArtifact { path: PathBuf },
+    #[error("Invalid chain: {0}")]
+    InvalidChain(String),
+    #[error("Contract name {name} in {} is not a valid identifier", path.display())]
+    InvalidContractName { name: String, path: PathBuf },
+    #[error("Io error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON-RPC error: {0}")]
+    JsonRpcError(JsonRpcError),
+    #[error("Middleware error: {0}")]
+    MiddlewareError(String),
+    #[error("Contract at {address:?} is missing selectors: {}", selectors.join(", "))]
+    MissingSelectors {
+        address: Address,
+        selectors: Vec<String>,
+    },
+    #[error("Registry chain is None")]
+    MissingChain,
+    #[error("Constructor arguments given but the abi has no constructor")]
+    MissingConstructor,
+    #[error("OUT_DIR is not set, expected a build script")]
+    MissingOutDir,
+    #[error("{transport} provider url is None")]
+    MissingUrl { transport: Transport },
+    #[error("No contract code at {address:?}")]
+    NoCode { address: Address },
+    #[error(
+        "No overload of {name} takes {arg_count} arguments, expected one of {}",
+        candidates.join(", ")
+    )]
+    NoMatchingOverload {
+        name: String,
+        arg_count: usize,
+        candidates: Vec<String>,
+    },
+    #[error("ChainId error: {0}")]
+    NamedChainError(#[from] num_enum::TryFromPrimitiveError<crate::named::NamedChain>),
     #[error("Provider error: {0}")]
-    ProviderError(#[from] ethers::providers::ProviderError),
+    ProviderError(ProviderError),
+    #[error("Revert error: {0}")]
+    Revert(Revert),
+    #[error("Serde error: {0}")]
+    SerdeJsonError(#[from] serde_json::Error),
+    #[error("Toml error: {0}")]
+    TomlError(#[from] toml::de::Error),
+    #[error("Toml error: {0}")]
+    TomlSerializeError(#[from] toml::ser::Error),
+    #[error("Transaction {tx_hash:?} dropped from mempool")]
+    TransactionDropped { tx_hash: H256 },
+    #[error("Transaction {0:?} not found")]
+    TransactionNotFound(H256),
+    #[error("Alias {alias} not found in the address book of chain ({chain_id})")]
+    UnknownAlias { alias: String, chain_id: u64 },
+    #[error("No provider registered for chain ({chain_id})")]
+    UnknownChain { chain_id: u64 },
+    #[error("{transport} provider url is not None: {url}")]
+    UnexpectedUrl { transport: Transport, url: String },
+    #[error("Event {0} not found in abi")]
+    UnknownEvent(String),
+    #[error("Function {0} not found in abi")]
+    UnknownFunction(String),
+    #[error("Bytecode in {} has unlinked library references", path.display())]
+    UnlinkedBytecode { path: PathBuf },
+    #[error("Unsupported file format: {0}")]
+    UnsupportedFormat(String),
     #[error("Url parse Error: {0}")]
     UrlParseError(#[from] url::ParseError),
+    #[error("Wallet error: {0}")]
+    WalletError(#[from] WalletError),
+    #[error("Yaml error: {0}")]
+    YamlError(#[from] serde_yaml_ng::Error),
 }
 
-impl<M> From<ethers::contract::ContractError<M>> for Error
+impl<M> From<ContractError<M>> for Error
 where
-    M: ethers::providers::Middleware,
+    M: Middleware,
 {
-    fn from(err: ethers::contract::ContractError<M>) -> Self {
-        Self::Error(err.to_string())
+    fn from(err: ContractError<M>) -> Self {
+        match err {
+            ContractError::Revert(data) => Self::Revert(Revert::from(data)),
+            ContractError::DecodingError(e) => Self::AbiError(e),
+            ContractError::DetokenizationError(e) => Self::DetokenizeError(e),
+            ContractError::ProviderError { e } => Self::from(e),
+            ContractError::MiddlewareError { e } => Self::from_middleware_error(e),
+            err => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl<M> From<MulticallError<M>> for Error
+where
+    M: Middleware,
+{
+    fn from(err: MulticallError<M>) -> Self {
+        match err {
+            MulticallError::ContractError(e) => Self::from(e),
+            err => Self::Error(err.to_string()),
+        }
+    }
+}
+
+impl From<ArchiveError> for Error {
+    fn from(err: ArchiveError) -> Self {
+        Self::ArchiveNodeRequired {
+            block: err.block,
+            message: err.message,
+        }
+    }
+}
+
+impl From<ProviderError> for Error {
+    fn from(err: ProviderError) -> Self {
+        match RpcError::as_error_response(&err).and_then(JsonRpcError::as_revert_data) {
+            Some(data) => Self::Revert(Revert::from(data)),
+            None => Self::ProviderError(err),
+        }
+    }
+}
+
+impl Error {
+    pub fn from_middleware_error<E: MiddlewareError>(err: E) -> Self {
+        let mut source = std::error::Error::source(&err);
+        while let Some(e) = source {
+            if let Some(archive) = e.downcast_ref::<ArchiveError>() {
+                return Self::from(archive.clone());
+            }
+            source = e.source();
+        }
+
+        match err.as_error_response() {
+            Some(response) => match response.as_revert_data() {
+                Some(data) => Self::Revert(Revert::from(data)),
+                None => Self::JsonRpcError(response.clone()),
+            },
+            None => Self::MiddlewareError(err.to_string()),
+        }
+    }
+
+    pub fn as_revert(&self) -> Option<&Revert> {
+        match self {
+            Error::Revert(revert) => Some(revert),
+            _ => None,
+        }
+    }
+
+    pub fn decode_contract_revert<E: ethers::contract::ContractRevert>(&self) -> Option<E> {
+        self.as_revert()?.decode_contract_revert()
+    }
+
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            Error::ProviderError(e) => RpcError::as_error_response(e),
+            Error::JsonRpcError(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub fn is_revert(&self) -> bool {
+        matches!(self, Error::Revert(_))
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        if let Error::ProviderError(ProviderError::HTTPError(e)) = self {
+            return e.status().is_some_and(|status| status.as_u16() == 429);
+        }
+
+        match self.as_error_response() {
+            Some(response) => {
+                let message = response.message.to_lowercase();
+                RATE_LIMIT_CODES.contains(&response.code)
+                    || RATE_LIMIT_MESSAGES.iter().any(|m| message.contains(m))
+            }
+            None => false,
+        }
+    }
+
+    /// The endpoint does not implement or has disabled the requested method.
+    pub fn is_method_not_found(&self) -> bool {
+        match self.as_error_response() {
+            Some(response) => {
+                let message = response.message.to_lowercase();
+                METHOD_NOT_FOUND_CODES.contains(&response.code)
+                    || message.contains("unsupported method")
+                    || (message.contains("method")
+                        && METHOD_NOT_FOUND_MESSAGES
+                            .iter()
+                            .any(|m| message.contains(m)))
+            }
+            None => false,
+        }
+    }
+
+    /// Rate limits, timeouts, connection failures and server errors are
+    /// considered transient. Reverts and malformed requests are not.
+    pub fn is_retryable(&self) -> bool {
+        if self.is_rate_limited() {
+            return true;
+        }
+
+        match self {
+            Error::ProviderError(ProviderError::HTTPError(e)) => {
+                e.is_timeout()
+                    || e.is_connect()
+                    || e.status().is_some_and(|status| status.is_server_error())
+            }
+            Error::ProviderError(ProviderError::JsonRpcClientError(e)) => {
+                e.as_error_response().is_none() && e.as_serde_error().is_none()
+            }
+            _ => false,
+        }
     }
 }
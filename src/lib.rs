@@ -1,27 +1,71 @@
+pub mod address_book;
+pub mod batch;
+#[cfg(feature = "build")]
+pub mod build;
+pub mod capabilities;
 pub mod chain;
+pub mod config;
+pub mod contract;
+pub mod decode;
+pub mod deploy;
+pub mod dynamic;
 pub mod error;
+pub mod multichain;
 pub mod named;
+pub mod pinned;
 pub mod providers;
+pub mod proxy;
 pub mod registry;
+pub mod revert;
+pub mod simulate;
+pub mod snapshot;
+pub mod trace;
+pub mod verify;
+
+#[doc(hidden)]
+pub use {
+    async_trait,
+    ethers_abirpc_derive::{__abirpc, __abirpc_transports},
+};
+
+pub use ethers_abirpc_derive::{abirpc_dir, abirpc_registry};
 
 pub mod prelude {
     pub use {
         crate::{
-            abirpc, address_from,
-            chain::{Chain, ChainConfig, RetryClientConfig},
+            abirpc, abirpc_dir, abirpc_registry,
+            address_book::AddressBook,
+            address_from,
+            capabilities::Capabilities,
+            chain::{BatchClientConfig, Chain, ChainConfig, RetryClientConfig},
+            config::{Config, Endpoint},
+            contract::{AbiContract, ContractRegistry},
+            decode::{DecodedCall, DecodedLog, DecodedTransaction, TxDecoder},
+            deploy::{Deployment, CREATE2_DEPLOYER},
+            dynamic::{DecodedEvent, DynamicContract, DynamicRegistry},
             error::Error,
+            multichain::MultiChainRegistry,
             named::NamedChain,
+            pinned::BlockPinned,
             providers::{
-                AbiProvider, AbiProviderTrait, HttpProvider, HttpTransport, IpcProvider,
-                IpcTransport, MockProvider, RetryProvider, RetryTransport, WsProvider, WsTransport,
+                AbiProvider, AbiProviderTrait, BatchProvider, BatchTransport, HttpProvider,
+                HttpTransport, IpcProvider, IpcTransport, MockProvider, RetryProvider,
+                RetryTransport, Transport, WsProvider, WsTransport,
             },
+            proxy::{ImplementationChange, ProxyInfo, ProxyKind},
+            registry::{RegistryConfig, RegistryMetrics},
+            revert::{PanicCode, Revert, RevertReason},
+            simulate::{BlockOverrides, Overrides, Simulation},
+            snapshot::{EntryMetadata, RegistrySnapshot, SnapshotEntry},
+            trace::DecodedFrame,
+            verify::Verification,
         },
         ethers::{
             contract::{abigen, Abigen, EthEvent},
             middleware::SignerMiddleware,
             providers::{Middleware, StreamExt},
             signers::{LocalWallet, Signer},
-            types::{BlockNumber, U256},
+            types::{BlockId, BlockNumber, U256},
         },
     };
 }
@@ -0,0 +1,141 @@
+use {
+    crate::{
+        capabilities::Capabilities,
+        chain::Chain,
+        error::Error,
+        providers::{AbiProvider, AbiProviderTrait},
+    },
+    dashmap::DashMap,
+    ethers::{providers::Middleware, types::Address},
+    std::{clone::Clone, sync::Arc},
+};
+
+pub type ChainProviders<M> = DashMap<u64, (Chain, Arc<M>)>;
+
+/// Contract registry spanning several chains. Entries are keyed by chain id and
+/// address, and each chain owns a single provider.
+#[derive(Debug)]
+pub struct MultiChainRegistry<M, C> {
+    pub providers: Arc<ChainProviders<M>>,
+    pub registry: Arc<DashMap<(u64, Address), C>>,
+    pub capabilities: Arc<DashMap<u64, Capabilities>>,
+}
+
+impl<M, C> Default for MultiChainRegistry<M, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M, C> MultiChainRegistry<M, C> {
+    pub fn new() -> Self {
+        Self {
+            providers: Arc::new(DashMap::new()),
+            registry: Arc::new(DashMap::new()),
+            capabilities: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub fn add_provider(&self, chain: Chain, provider: M) {
+        self.providers
+            .insert(chain.id(), (chain, Arc::new(provider)));
+    }
+
+    pub fn provider(&self, chain: Chain) -> Option<Arc<M>> {
+        self.providers
+            .get(&chain.id())
+            .map(|entry| Arc::clone(&entry.1))
+    }
+
+    pub fn chains(&self) -> Vec<Chain> {
+        self.providers.iter().map(|entry| entry.0).collect()
+    }
+
+    /// Capabilities probed when the provider of `chain` was added by `add_chain`.
+    pub fn capabilities(&self, chain: Chain) -> Option<Capabilities> {
+        self.capabilities.get(&chain.id()).map(|entry| *entry)
+    }
+
+    pub fn entry_exists(&self, chain: Chain, address: Address) -> bool {
+        self.registry.contains_key(&(chain.id(), address))
+    }
+
+    pub fn add_entry(&self, chain: Chain, address: Address, contract: C) {
+        self.registry.insert((chain.id(), address), contract);
+    }
+
+    fn chain(&self, chain_id: u64) -> Chain {
+        self.providers
+            .get(&chain_id)
+            .map(|entry| entry.0)
+            .unwrap_or(Chain::Id(chain_id))
+    }
+}
+
+impl<M, C> MultiChainRegistry<M, C>
+where
+    C: Clone,
+{
+    pub fn get_entry(&self, chain: Chain, address: Address) -> Option<C> {
+        self.registry
+            .get(&(chain.id(), address))
+            .map(|entry| entry.clone())
+    }
+
+    /// Returns the registered contract, or inserts the one built by `contract`
+    /// under a single lock.
+    pub fn get_or_insert_with(
+        &self,
+        chain: Chain,
+        address: Address,
+        contract: impl FnOnce() -> C,
+    ) -> C {
+        self.registry
+            .entry((chain.id(), address))
+            .or_insert_with(contract)
+            .clone()
+    }
+
+    pub fn deployments(&self, address: Address) -> Vec<(Chain, C)> {
+        self.entries()
+            .into_iter()
+            .filter(|((_, entry_address), _)| *entry_address == address)
+            .map(|((chain, _), contract)| (chain, contract))
+            .collect()
+    }
+
+    pub fn entries(&self) -> Vec<((Chain, Address), C)> {
+        let entries: Vec<_> = self
+            .registry
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+
+        entries
+            .into_iter()
+            .map(|((chain_id, address), contract)| ((self.chain(chain_id), address), contract))
+            .collect()
+    }
+}
+
+impl<M, C> MultiChainRegistry<M, C>
+where
+    M: Middleware,
+    AbiProvider: AbiProviderTrait<M>,
+{
+    pub async fn add_chain(&self, url: String, chain: Chain) -> Result<(), Error> {
+        let abi_provider = AbiProvider::new(url, chain);
+        let provider = abi_provider.provider().await?;
+        self.add_provider(chain, provider);
+        match abi_provider.capabilities() {
+            Some(capabilities) => {
+                self.capabilities.insert(chain.id(), capabilities);
+            }
+            None => {
+                self.capabilities.remove(&chain.id());
+            }
+        }
+
+        Ok(())
+    }
+}
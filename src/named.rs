@@ -1,4 +1,8 @@
-use core::time::Duration;
+use {
+    crate::chain::{Chain, ChainKey},
+    core::time::Duration,
+    serde::{de, Deserialize, Deserializer, Serialize, Serializer},
+};
 
 /// An Ethereum EIP-155 chain.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, strum::IntoStaticStr)] // Into<&'static str>, AsRef<str>, fmt::Display and serde::Serialize
@@ -113,6 +117,7 @@ pub enum NamedChain {
     KaruraTestnet = 596,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for NamedChain {
     fn default() -> Self {
         Self::Mainnet
@@ -125,6 +130,25 @@ impl AsRef<str> for NamedChain {
         self.as_str()
     }
 }
+
+/// Written as the chain name. Names are matched case insensitively and chain
+/// ids are accepted when reading.
+impl Serialize for NamedChain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for NamedChain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let chain =
+            Chain::try_from(ChainKey::deserialize(deserializer)?).map_err(de::Error::custom)?;
+        chain
+            .named()
+            .ok_or_else(|| de::Error::custom(format!("Unknown chain id: {}", chain.id())))
+    }
+}
+
 impl NamedChain {
     pub fn as_str(&self) -> &'static str {
         self.into()
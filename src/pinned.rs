@@ -0,0 +1,253 @@
+use {
+    crate::error::Error,
+    async_trait::async_trait,
+    ethers::{
+        contract::Multicall,
+        providers::{Middleware, MiddlewareError},
+        types::{
+            transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
+            Address, BlockId, Bytes, EIP1186ProofResponse, GethDebugTracingCallOptions, GethTrace,
+            NameOrAddress, H256, U256,
+        },
+    },
+    std::{any::Any, sync::Arc},
+    thiserror::Error,
+};
+
+// Error messages returned by common clients when historical state has been pruned
+const ARCHIVE_ERRORS: [&str; 7] = [
+    "missing trie node",
+    "header not found",
+    "historical state",
+    "state is not available",
+    "state not available",
+    "pruned",
+    "old data not available",
+];
+
+/// State at `block` has been pruned. Kept as the source of
+/// `BlockPinnedError::ArchiveNodeRequired`, so the classification survives the
+/// conversion of contract and multicall errors into `Error::ArchiveNodeRequired`.
+#[derive(Error, Debug, Clone)]
+#[error("State at block {block:?} is unavailable, an archive node is required: {message}")]
+pub struct ArchiveError {
+    pub block: BlockId,
+    pub message: String,
+}
+
+#[derive(Error, Debug)]
+pub enum BlockPinnedError<M: Middleware> {
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+    #[error("{archive}")]
+    ArchiveNodeRequired {
+        #[source]
+        archive: ArchiveError,
+        error: M::Error,
+    },
+}
+
+impl<M: Middleware> MiddlewareError for BlockPinnedError<M> {
+    type Inner = M::Error;
+
+    fn from_err(src: M::Error) -> Self {
+        BlockPinnedError::MiddlewareError(src)
+    }
+
+    fn as_inner(&self) -> Option<&Self::Inner> {
+        match self {
+            BlockPinnedError::MiddlewareError(e) => Some(e),
+            BlockPinnedError::ArchiveNodeRequired { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Block of `client` if it is a `BlockPinned` middleware.
+pub fn pinned_block<M>(client: &M) -> Option<BlockId>
+where
+    M: Middleware + 'static,
+    M::Inner: 'static,
+{
+    (client as &dyn Any)
+        .downcast_ref::<BlockPinned<M::Inner>>()
+        .map(BlockPinned::block)
+}
+
+/// Middleware which sends every state read that does not specify a block
+/// with a fixed `BlockId`.
+#[derive(Debug)]
+pub struct BlockPinned<M> {
+    inner: Arc<M>,
+    block: BlockId,
+}
+
+impl<M> Clone for BlockPinned<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            block: self.block,
+        }
+    }
+}
+
+impl<M> BlockPinned<M>
+where
+    M: Middleware,
+{
+    pub fn new(inner: impl Into<Arc<M>>, block: impl Into<BlockId>) -> Self {
+        Self {
+            inner: inner.into(),
+            block: block.into(),
+        }
+    }
+
+    pub fn block(&self) -> BlockId {
+        self.block
+    }
+
+    pub async fn check_archive(&self) -> Result<(), Error> {
+        match self.get_balance(Address::zero(), None).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Error::from_middleware_error(e)),
+        }
+    }
+
+    pub async fn multicall(self, address: Option<Address>) -> Result<Multicall<Self>, Error> {
+        Ok(Multicall::new(self, address).await?)
+    }
+
+    fn pin(&self, block: Option<BlockId>) -> Option<BlockId> {
+        block.or(Some(self.block))
+    }
+
+    fn map_err(&self, err: M::Error) -> BlockPinnedError<M> {
+        let message = err.to_string().to_lowercase();
+        if ARCHIVE_ERRORS.iter().any(|e| message.contains(e)) {
+            BlockPinnedError::ArchiveNodeRequired {
+                archive: ArchiveError {
+                    block: self.block,
+                    message: err.to_string(),
+                },
+                error: err,
+            }
+        } else {
+            BlockPinnedError::MiddlewareError(err)
+        }
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for BlockPinned<M>
+where
+    M: Middleware,
+{
+    type Error = BlockPinnedError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn call(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner
+            .call(tx, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn estimate_gas(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.inner
+            .estimate_gas(tx, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn create_access_list(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<AccessListWithGasUsed, Self::Error> {
+        self.inner
+            .create_access_list(tx, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn get_balance<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.inner
+            .get_balance(from, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn get_transaction_count<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        self.inner
+            .get_transaction_count(from, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        at: T,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner
+            .get_code(at, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn get_storage_at<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        location: H256,
+        block: Option<BlockId>,
+    ) -> Result<H256, Self::Error> {
+        self.inner
+            .get_storage_at(from, location, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        locations: Vec<H256>,
+        block: Option<BlockId>,
+    ) -> Result<EIP1186ProofResponse, Self::Error> {
+        self.inner
+            .get_proof(from, locations, self.pin(block))
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+
+    async fn debug_trace_call<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        req: T,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingCallOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        self.inner
+            .debug_trace_call(req, self.pin(block), trace_options)
+            .await
+            .map_err(|e| self.map_err(e))
+    }
+}
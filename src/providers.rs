@@ -1,6 +1,8 @@
 use {
     crate::{
-        chain::{Chain, RetryClientConfig},
+        batch::BatchHttp,
+        capabilities::Capabilities,
+        chain::{BatchClientConfig, Chain, RetryClientConfig},
         error::Error,
     },
     async_trait::async_trait,
@@ -11,10 +13,22 @@ use {
         },
         types::U256,
     },
-    std::{path::Path, time::Duration},
+    reqwest::Client,
+    std::{path::Path, sync::OnceLock, time::Duration},
+    strum_macros::Display,
     url::Url,
 };
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Display)]
+pub enum Transport {
+    Ws,
+    Ipc,
+    Http,
+    Retry,
+    Batch,
+    Mock,
+}
+
 pub type HttpTransport = Http;
 
 pub type HttpProvider = Provider<HttpTransport>;
@@ -31,6 +45,10 @@ pub type RetryTransport = RetryClient<Http>;
 
 pub type RetryProvider = Provider<RetryTransport>;
 
+pub type BatchTransport = BatchHttp;
+
+pub type BatchProvider = Provider<BatchTransport>;
+
 pub type MockProvider = Provider<ethers::providers::MockProvider>;
 
 #[async_trait]
@@ -44,25 +62,47 @@ where
 pub struct AbiProvider {
     pub url: Option<String>,
     pub chain: Option<Chain>,
+    capabilities: OnceLock<Capabilities>,
 }
 
 impl AbiProvider {
     pub fn _new(url: Option<String>, chain: Option<Chain>) -> Self {
-        Self { url, chain }
+        Self {
+            url,
+            chain,
+            capabilities: OnceLock::new(),
+        }
     }
 
     pub fn new(url: String, chain: Chain) -> Self {
-        Self {
-            url: Some(url),
-            chain: Some(chain),
-        }
+        Self::_new(Some(url), Some(chain))
     }
 
     pub fn mock() -> Self {
-        Self {
-            url: None,
-            chain: None,
-        }
+        Self::_new(None, None)
+    }
+
+    /// Uses `capabilities` instead of probing the endpoint again.
+    pub fn with_capabilities(self, capabilities: Capabilities) -> Self {
+        let _ = self.capabilities.set(capabilities);
+        self
+    }
+
+    /// Capabilities probed by the first provider built, see `ChainConfig::probe_capabilities`.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.capabilities.get().copied()
+    }
+
+    fn probes_capabilities(&self) -> bool {
+        self.chain.is_some_and(|chain| chain.probe_capabilities())
+            && self.capabilities.get().is_none()
+    }
+}
+
+fn batch_config(chain: Option<Chain>) -> BatchClientConfig {
+    match chain {
+        Some(chain) => chain.batch_client_config(),
+        None => BatchClientConfig::default(),
     }
 }
 
@@ -72,18 +112,25 @@ macro_rules! assert_chain_id {
             if chain.assert_chain_id() {
                 let provider_chain_id = $provider.get_chainid().await?;
                 if U256::from(chain.id()) != provider_chain_id {
-                    let e = format!(
-                        "Configured chain_id ({}) does not match chain ({})",
-                        U256::from(chain.id()),
-                        provider_chain_id
-                    );
-                    return Err(Error::ChainIdError(e));
+                    return Err(Error::ChainIdMismatch {
+                        expected: chain.id(),
+                        actual: provider_chain_id,
+                    });
                 }
             }
         }
     };
 }
 
+macro_rules! probe_capabilities {
+    ($self: expr, $provider: expr, $transport: expr, $batch: expr) => {
+        if $self.probes_capabilities() {
+            let capabilities = Capabilities::probe(&$provider, $transport, $batch).await;
+            let _ = $self.capabilities.set(capabilities);
+        }
+    };
+}
+
 #[async_trait]
 impl AbiProviderTrait<WsProvider> for AbiProvider {
     async fn provider(&self) -> Result<WsProvider, Error> {
@@ -92,9 +139,12 @@ impl AbiProviderTrait<WsProvider> for AbiProvider {
                 let url = Url::parse(url)?;
                 let provider = Provider::<WsTransport>::connect(url).await?;
                 assert_chain_id!(self.chain, provider);
+                probe_capabilities!(self, provider, Transport::Ws, None);
                 Ok(provider)
             }
-            None => Err(Error::Error(String::from("Provider url is None"))),
+            None => Err(Error::MissingUrl {
+                transport: Transport::Ws,
+            }),
         }
     }
 }
@@ -106,9 +156,12 @@ impl AbiProviderTrait<IpcProvider> for AbiProvider {
             Some(url) => {
                 let provider = Provider::<IpcTransport>::connect_ipc(Path::new(&url)).await?;
                 assert_chain_id!(self.chain, provider);
+                probe_capabilities!(self, provider, Transport::Ipc, None);
                 Ok(provider)
             }
-            None => Err(Error::Error(String::from("Provider url is None"))),
+            None => Err(Error::MissingUrl {
+                transport: Transport::Ipc,
+            }),
         }
     }
 }
@@ -119,11 +172,20 @@ impl AbiProviderTrait<HttpProvider> for AbiProvider {
         match &self.url {
             Some(url) => {
                 let url = Url::parse(url)?;
-                let provider = Provider::<HttpTransport>::new(Http::new(url));
+                let client = Client::new();
+                let batch = BatchHttp::new_with_client(
+                    url.clone(),
+                    batch_config(self.chain),
+                    client.clone(),
+                );
+                let provider = Provider::<HttpTransport>::new(Http::new_with_client(url, client));
                 assert_chain_id!(self.chain, provider);
+                probe_capabilities!(self, provider, Transport::Http, Some(&batch));
                 Ok(provider)
             }
-            None => Err(Error::Error(String::from("Provider url is None"))),
+            None => Err(Error::MissingUrl {
+                transport: Transport::Http,
+            }),
         }
     }
 }
@@ -139,17 +201,48 @@ impl AbiProviderTrait<RetryProvider> for AbiProvider {
                     None => RetryClientConfig::default(),
                 };
 
+                let client = Client::new();
+                let batch = BatchHttp::new_with_client(
+                    url.clone(),
+                    batch_config(self.chain),
+                    client.clone(),
+                );
                 let provider = Provider::new(
                     RetryClientBuilder::default()
                         .rate_limit_retries(retry_config.rate_limit_retries)
                         .timeout_retries(retry_config.timeout_retries)
                         .initial_backoff(Duration::from_millis(retry_config.initial_backoff_ms))
-                        .build(Http::new(url), Box::new(HttpRateLimitRetryPolicy)),
+                        .build(
+                            Http::new_with_client(url, client),
+                            Box::new(HttpRateLimitRetryPolicy),
+                        ),
                 );
                 assert_chain_id!(self.chain, provider);
+                probe_capabilities!(self, provider, Transport::Retry, Some(&batch));
+                Ok(provider)
+            }
+            None => Err(Error::MissingUrl {
+                transport: Transport::Retry,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl AbiProviderTrait<BatchProvider> for AbiProvider {
+    async fn provider(&self) -> Result<BatchProvider, Error> {
+        match &self.url {
+            Some(url) => {
+                let url = Url::parse(url)?;
+                let batch = BatchHttp::new(url, batch_config(self.chain));
+                let provider = Provider::new(batch.clone());
+                assert_chain_id!(self.chain, provider);
+                probe_capabilities!(self, provider, Transport::Batch, Some(&batch));
                 Ok(provider)
             }
-            None => Err(Error::Error(String::from("Provider url is None"))),
+            None => Err(Error::MissingUrl {
+                transport: Transport::Batch,
+            }),
         }
     }
 }
@@ -158,7 +251,10 @@ impl AbiProviderTrait<RetryProvider> for AbiProvider {
 impl AbiProviderTrait<MockProvider> for AbiProvider {
     async fn provider(&self) -> Result<MockProvider, Error> {
         match &self.url {
-            Some(_) => Err(Error::Error(String::from("MockProvider url is not None"))),
+            Some(url) => Err(Error::UnexpectedUrl {
+                transport: Transport::Mock,
+                url: url.clone(),
+            }),
             None => {
                 let (provider, _mock) = Provider::mocked();
                 Ok(provider)
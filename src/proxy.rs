@@ -0,0 +1,146 @@
+use {
+    crate::error::Error,
+    ethers::{
+        providers::Middleware,
+        types::{Address, Bytes, TransactionRequest, H256},
+    },
+    serde::{Deserialize, Serialize},
+};
+
+// bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)
+const EIP1967_IMPLEMENTATION_SLOT: [u8; 32] = [
+    0x36, 0x08, 0x94, 0xa1, 0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d,
+    0xca, 0x3e, 0x20, 0x76, 0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc,
+];
+// bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)
+const EIP1967_ADMIN_SLOT: [u8; 32] = [
+    0xb5, 0x31, 0x27, 0x68, 0x4a, 0x56, 0x8b, 0x31, 0x73, 0xae, 0x13, 0xb9, 0xf8, 0xa6, 0x01, 0x6e,
+    0x24, 0x3e, 0x63, 0xb6, 0xe8, 0xee, 0x11, 0x78, 0xd6, 0xa7, 0x17, 0x85, 0x0b, 0x5d, 0x61, 0x03,
+];
+// bytes32(uint256(keccak256("eip1967.proxy.beacon")) - 1)
+const EIP1967_BEACON_SLOT: [u8; 32] = [
+    0xa3, 0xf0, 0xad, 0x74, 0xe5, 0x42, 0x3a, 0xeb, 0xfd, 0x80, 0xd3, 0xef, 0x43, 0x46, 0x57, 0x83,
+    0x35, 0xa9, 0xa7, 0x2a, 0xea, 0xee, 0x59, 0xff, 0x6c, 0xb3, 0x58, 0x2b, 0x35, 0x13, 0x3d, 0x50,
+];
+// keccak256("PROXIABLE")
+const EIP1822_PROXIABLE_SLOT: [u8; 32] = [
+    0xc5, 0xf1, 0x6f, 0x0f, 0xcc, 0x63, 0x9f, 0xa4, 0x8a, 0x69, 0x47, 0x83, 0x6d, 0x98, 0x50, 0xf5,
+    0x04, 0x79, 0x85, 0x23, 0xbf, 0x8c, 0x9a, 0x3a, 0x87, 0xd5, 0x87, 0x6c, 0xf6, 0x22, 0xbc, 0xf7,
+];
+// implementation()
+const BEACON_IMPLEMENTATION_SELECTOR: [u8; 4] = [0x5c, 0x60, 0xda, 0x1b];
+
+const EIP1167_PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+const EIP1167_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProxyKind {
+    Eip1967,
+    Eip1967Beacon,
+    Eip1822,
+    Eip1167,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ProxyInfo {
+    pub kind: ProxyKind,
+    pub implementation: Address,
+    pub admin: Option<Address>,
+    pub beacon: Option<Address>,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ImplementationChange {
+    pub address: Address,
+    pub previous: Option<Address>,
+    pub current: Option<Address>,
+}
+
+/// Detects EIP-1167 minimal proxies from their runtime bytecode, then
+/// EIP-1967 (direct and beacon) and EIP-1822 proxies from their storage slots.
+pub async fn detect_proxy<M: Middleware>(
+    client: &M,
+    address: Address,
+) -> Result<Option<ProxyInfo>, Error> {
+    let code = client
+        .get_code(address, None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    if let Some(implementation) = minimal_proxy_implementation(&code) {
+        return Ok(Some(ProxyInfo {
+            kind: ProxyKind::Eip1167,
+            implementation,
+            admin: None,
+            beacon: None,
+        }));
+    }
+
+    if let Some(implementation) = read_slot(client, address, EIP1967_IMPLEMENTATION_SLOT).await? {
+        let admin = read_slot(client, address, EIP1967_ADMIN_SLOT).await?;
+        return Ok(Some(ProxyInfo {
+            kind: ProxyKind::Eip1967,
+            implementation,
+            admin,
+            beacon: None,
+        }));
+    }
+
+    if let Some(beacon) = read_slot(client, address, EIP1967_BEACON_SLOT).await? {
+        let tx = TransactionRequest::new()
+            .to(beacon)
+            .data(Bytes::from(BEACON_IMPLEMENTATION_SELECTOR.to_vec()));
+        let output = client
+            .call(&tx.into(), None)
+            .await
+            .map_err(Error::from_middleware_error)?;
+        let implementation = output
+            .get(..32)
+            .map(|word| Address::from(H256::from_slice(word)))
+            .filter(|implementation| !implementation.is_zero());
+
+        return Ok(implementation.map(|implementation| ProxyInfo {
+            kind: ProxyKind::Eip1967Beacon,
+            implementation,
+            admin: None,
+            beacon: Some(beacon),
+        }));
+    }
+
+    if let Some(implementation) = read_slot(client, address, EIP1822_PROXIABLE_SLOT).await? {
+        return Ok(Some(ProxyInfo {
+            kind: ProxyKind::Eip1822,
+            implementation,
+            admin: None,
+            beacon: None,
+        }));
+    }
+
+    Ok(None)
+}
+
+fn minimal_proxy_implementation(code: &[u8]) -> Option<Address> {
+    let implementation = code.strip_prefix(&EIP1167_PREFIX[..])?;
+    let (implementation, suffix) = implementation.split_at_checked(20)?;
+    if suffix != EIP1167_SUFFIX {
+        return None;
+    }
+
+    Some(Address::from_slice(implementation))
+}
+
+async fn read_slot<M: Middleware>(
+    client: &M,
+    address: Address,
+    slot: [u8; 32],
+) -> Result<Option<Address>, Error> {
+    let value = client
+        .get_storage_at(address, H256::from(slot), None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    let value = Address::from(value);
+
+    Ok((!value.is_zero()).then_some(value))
+}
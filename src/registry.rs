@@ -1,199 +1,552 @@
 use {
-    crate::chain::Chain,
-    ethers::types::Address,
+    crate::{
+        capabilities::Capabilities,
+        chain::Chain,
+        decode::{DecoderSource, TxDecoder},
+        providers::AbiProvider,
+        snapshot::{EntryMetadata, RegistrySnapshot, SnapshotEntry},
+    },
+    dashmap::{mapref::entry::Entry, DashMap},
+    ethers::{contract::BaseContract, types::Address},
     std::{
         clone::Clone,
-        collections::HashMap,
-        sync::{Arc, RwLock},
+        collections::BTreeSet,
+        fmt,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex, MutexGuard, PoisonError,
+        },
+        time::{Duration, Instant},
     },
 };
 
+/// Bounds on registry storage. Once `capacity` entries are registered the
+/// least recently used entry is evicted. Entries older than `ttl` are treated
+/// as absent and dropped on the next write.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct RegistryConfig {
+    pub capacity: Option<usize>,
+    pub ttl: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct RegistryMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub expirations: u64,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+}
+
+#[derive(Debug)]
+pub struct RegistryEntry<C> {
+    pub contract: C,
+    pub inserted_at: Instant,
+    last_used: AtomicU64,
+}
+
+impl<C> RegistryEntry<C> {
+    fn is_expired(&self, ttl: Option<Duration>) -> bool {
+        ttl.is_some_and(|ttl| self.inserted_at.elapsed() > ttl)
+    }
+}
+
+/// Entries ordered by last use and by insertion, maintained only for the
+/// configured bounds. Positions may be stale after concurrent updates, so
+/// popped positions are checked against the entry before it is removed.
+#[derive(Debug, Default)]
+struct Index {
+    recency: BTreeSet<(u64, Address)>,
+    insertion: BTreeSet<(Instant, Address)>,
+}
+
+/// Decoder view of a registry's entries, see `AbiRegistry::add_to_decoder`.
+struct RegistrySource<C> {
+    name: &'static str,
+    registry: Arc<DashMap<Address, RegistryEntry<C>>>,
+    ttl: Option<Duration>,
+    base: fn(&C) -> Arc<BaseContract>,
+}
+
+impl<C> fmt::Debug for RegistrySource<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistrySource")
+            .field("name", &self.name)
+            .field("len", &self.registry.len())
+            .finish()
+    }
+}
+
+impl<C> DecoderSource for RegistrySource<C>
+where
+    C: Send + Sync,
+{
+    fn id(&self) -> usize {
+        Arc::as_ptr(&self.registry) as usize
+    }
+
+    fn get(&self, address: Address) -> Option<(String, Arc<BaseContract>)> {
+        let entry = self.registry.get(&address)?;
+        if entry.is_expired(self.ttl) {
+            return None;
+        }
+
+        Some((self.name.to_string(), (self.base)(&entry.contract)))
+    }
+
+    fn addresses(&self) -> Vec<Address> {
+        self.registry
+            .iter()
+            .filter(|entry| !entry.is_expired(self.ttl))
+            .map(|entry| *entry.key())
+            .collect()
+    }
+}
+
+/// Contract storage shared by generated registries. Maps are sharded and
+/// lock-free from the caller's perspective, so a panicking task cannot leave
+/// the registry unusable.
 #[derive(Debug)]
 pub struct AbiRegistry<C> {
     pub url: Option<String>,
     pub chain: Option<Chain>,
-    pub registry: Arc<RwLock<HashMap<Address, C>>>,
+    pub config: RegistryConfig,
+    pub registry: Arc<DashMap<Address, RegistryEntry<C>>>,
+    pub aliases: Arc<DashMap<String, Address>>,
+    pub metadata: Arc<DashMap<Address, EntryMetadata>>,
+    capabilities: Mutex<Option<Capabilities>>,
+    metrics: Arc<Metrics>,
+    clock: Arc<AtomicU64>,
+    index: Arc<Mutex<Index>>,
 }
 
 impl<C> AbiRegistry<C> {
-    pub fn new(url: String, chain: Chain) -> Self {
+    pub fn _new(url: Option<String>, chain: Option<Chain>) -> Self {
         Self {
-            url: Some(url),
-            chain: Some(chain),
-            registry: Arc::new(RwLock::new(HashMap::new())),
+            url,
+            chain,
+            config: RegistryConfig::default(),
+            registry: Arc::new(DashMap::new()),
+            aliases: Arc::new(DashMap::new()),
+            metadata: Arc::new(DashMap::new()),
+            capabilities: Mutex::new(None),
+            metrics: Arc::new(Metrics::default()),
+            clock: Arc::new(AtomicU64::new(0)),
+            index: Arc::new(Mutex::new(Index::default())),
         }
     }
 
+    pub fn new(url: String, chain: Chain) -> Self {
+        Self::_new(Some(url), Some(chain))
+    }
+
     pub fn mock() -> Self {
-        Self {
-            url: None,
-            chain: None,
-            registry: Arc::new(RwLock::new(HashMap::new())),
+        Self::_new(None, None)
+    }
+
+    pub fn with_config(mut self, config: RegistryConfig) -> Self {
+        self.config = config;
+        *self.index() = Index::default();
+        for entry in self.registry.iter() {
+            self.index_entry(*entry.key(), entry.value());
         }
+
+        self
     }
 
-    pub fn entry_exists(&self, address: Address) -> bool {
-        let arc_clone = Arc::clone(&self.registry);
-        let registry = arc_clone.read().expect("Registry RwLock poisoned!");
-        let entry_exists = registry.contains_key(&address);
-        drop(registry);
+    pub fn metrics(&self) -> RegistryMetrics {
+        RegistryMetrics {
+            hits: self.metrics.hits.load(Ordering::Relaxed),
+            misses: self.metrics.misses.load(Ordering::Relaxed),
+            evictions: self.metrics.evictions.load(Ordering::Relaxed),
+            expirations: self.metrics.expirations.load(Ordering::Relaxed),
+        }
+    }
 
-        entry_exists
+    /// Resolves addresses in `decoder` against this registry's entries, so
+    /// entries registered later are decoded too. `base` returns the decoding
+    /// contract of an entry.
+    pub fn add_to_decoder(
+        &self,
+        decoder: &TxDecoder,
+        name: &'static str,
+        base: fn(&C) -> Arc<BaseContract>,
+    ) where
+        C: Send + Sync + 'static,
+    {
+        decoder.add_source(Arc::new(RegistrySource {
+            name,
+            registry: Arc::clone(&self.registry),
+            ttl: self.config.ttl,
+            base,
+        }));
     }
 
-    pub fn add_entry(&self, address: Address, contract: C) {
-        let arc_clone = Arc::clone(&self.registry);
-        let mut registry = arc_clone.write().expect("Registry RwLock poisoned!");
-        registry.insert(address, contract);
-        drop(registry);
+    /// Capabilities probed by the last provider built for the registry.
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        *self
+            .capabilities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
-}
 
-#[macro_export]
-macro_rules! abirpc {
-    ($abi:ident) => {
-        paste::paste! {
-
-            #[derive(Debug)]
-            pub struct [<$abi Registry>]<M>($crate::registry::AbiRegistry<$abi<M>>)
-            where
-                M: ::ethers::prelude::Middleware;
-
-            #[async_trait::async_trait]
-            impl $crate::providers::AbiProviderTrait<$crate::providers::WsProvider>
-                for [<$abi Registry>]<$crate::providers::WsProvider>
-            {
-                async fn provider(
-                    &self,
-                ) -> Result<$crate::providers::WsProvider, $crate::error::Error> {
-                    let provider: $crate::providers::WsProvider =
-                        $crate::providers::AbiProvider::_new(self.0.url.clone(), self.0.chain)
-                            .provider()
-                            .await?;
-
-                    Ok(provider)
-                }
-            }
+    pub fn set_capabilities(&self, capabilities: Capabilities) {
+        *self
+            .capabilities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(capabilities);
+    }
 
-            #[async_trait::async_trait]
-            impl $crate::providers::AbiProviderTrait<$crate::providers::IpcProvider>
-                for [<$abi Registry>]<$crate::providers::IpcProvider>
-            {
-                async fn provider(
-                    &self,
-                ) -> Result<$crate::providers::IpcProvider, $crate::error::Error> {
-                    let provider: $crate::providers::IpcProvider =
-                        $crate::providers::AbiProvider::_new(self.0.url.clone(), self.0.chain)
-                            .provider()
-                            .await?;
-
-                    Ok(provider)
-                }
-            }
+    /// Provider for the registry endpoint, reusing probed capabilities.
+    pub fn abi_provider(&self) -> AbiProvider {
+        let abi_provider = AbiProvider::_new(self.url.clone(), self.chain);
+        match self.capabilities() {
+            Some(capabilities) => abi_provider.with_capabilities(capabilities),
+            None => abi_provider,
+        }
+    }
 
-            #[async_trait::async_trait]
-            impl $crate::providers::AbiProviderTrait<$crate::providers::HttpProvider>
-                for [<$abi Registry>]<$crate::providers::HttpProvider>
-            {
-                async fn provider(
-                    &self,
-                ) -> Result<$crate::providers::HttpProvider, $crate::error::Error> {
-                    let provider: $crate::providers::HttpProvider =
-                        $crate::providers::AbiProvider::_new(self.0.url.clone(), self.0.chain)
-                            .provider()
-                            .await?;
-
-                    Ok(provider)
-                }
+    pub fn entry_exists(&self, address: Address) -> bool {
+        self.registry
+            .get(&address)
+            .is_some_and(|entry| !entry.is_expired(self.config.ttl))
+    }
+
+    pub fn add_entry(&self, address: Address, contract: C) {
+        let entry = self.new_entry(contract);
+        match self.registry.entry(address) {
+            Entry::Occupied(mut occupied) => {
+                let previous = occupied.insert(entry);
+                self.unindex_entry(address, &previous);
+                self.index_entry(address, occupied.get());
+            }
+            Entry::Vacant(vacant) => {
+                let entry = vacant.insert(entry);
+                self.index_entry(address, &entry);
             }
+        }
+
+        self.enforce_bounds();
+    }
+
+    pub fn remove_entry(&self, address: Address) -> Option<C> {
+        let entry = self.registry.remove(&address);
+        if let Some((_, entry)) = &entry {
+            self.unindex_entry(address, entry);
+        }
+        self.forget(&[address]);
+
+        entry
+            .filter(|(_, entry)| !entry.is_expired(self.config.ttl))
+            .map(|(_, entry)| entry.contract)
+    }
+
+    /// Drops expired entries and returns how many were removed.
+    pub fn purge_expired(&self) -> usize {
+        let removed = self.remove_expired();
+        self.forget(&removed);
+
+        removed.len()
+    }
+
+    /// Attaches `alias` to `address`. An alias refers to a single address, so
+    /// reusing an alias moves it to the new address.
+    pub fn add_alias(&self, alias: impl Into<String>, address: Address) -> Option<Address> {
+        self.aliases.insert(alias.into(), address)
+    }
+
+    pub fn remove_alias(&self, alias: &str) -> Option<Address> {
+        self.aliases.remove(alias).map(|(_, address)| address)
+    }
+
+    pub fn resolve_alias(&self, alias: &str) -> Option<Address> {
+        self.aliases.get(alias).map(|address| *address)
+    }
+
+    pub fn aliases_of(&self, address: Address) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .aliases
+            .iter()
+            .filter(|alias| *alias.value() == address)
+            .map(|alias| alias.key().clone())
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    pub fn aliases(&self) -> Vec<(String, Address)> {
+        let mut entries: Vec<(String, Address)> = self
+            .aliases
+            .iter()
+            .map(|alias| (alias.key().clone(), *alias.value()))
+            .collect();
+
+        entries.sort();
+        entries
+    }
+
+    pub fn addresses(&self) -> Vec<Address> {
+        self.registry
+            .iter()
+            .filter(|entry| !entry.is_expired(self.config.ttl))
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.registry
+            .iter()
+            .filter(|entry| !entry.is_expired(self.config.ttl))
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.registry.clear();
+        *self.index() = Index::default();
+        self.aliases.clear();
+        self.metadata.clear();
+    }
+
+    pub fn set_metadata(&self, address: Address, entry_metadata: EntryMetadata) {
+        self.metadata.insert(address, entry_metadata);
+    }
+
+    pub fn update_metadata(&self, address: Address, update: impl FnOnce(&mut EntryMetadata)) {
+        update(&mut self.metadata.entry(address).or_default());
+    }
+
+    pub fn get_metadata(&self, address: Address) -> Option<EntryMetadata> {
+        self.metadata.get(&address).map(|metadata| metadata.clone())
+    }
+
+    /// Entries are ordered by address so that snapshots are stable across runs.
+    pub fn snapshot(&self, abi: &str) -> RegistrySnapshot {
+        let mut addresses = self.addresses();
+        addresses.sort();
+
+        let entries = addresses
+            .into_iter()
+            .map(|address| SnapshotEntry {
+                address,
+                aliases: self.aliases_of(address),
+                metadata: self.get_metadata(address).unwrap_or_default(),
+            })
+            .collect();
+
+        RegistrySnapshot {
+            abi: abi.to_string(),
+            chain_id: self.chain.map(|chain| chain.id()),
+            entries,
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn new_entry(&self, contract: C) -> RegistryEntry<C> {
+        RegistryEntry {
+            contract,
+            inserted_at: Instant::now(),
+            last_used: AtomicU64::new(self.tick()),
+        }
+    }
+
+    fn index(&self) -> MutexGuard<'_, Index> {
+        // The index holds no invariant a panic could break
+        self.index.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn index_entry(&self, address: Address, entry: &RegistryEntry<C>) {
+        let mut index = self.index();
+        if self.config.capacity.is_some() {
+            let last_used = entry.last_used.load(Ordering::Relaxed);
+            index.recency.insert((last_used, address));
+        }
+        if self.config.ttl.is_some() {
+            index.insertion.insert((entry.inserted_at, address));
+        }
+    }
 
-            #[async_trait::async_trait]
-            impl $crate::providers::AbiProviderTrait<$crate::providers::RetryProvider>
-                for [<$abi Registry>]<$crate::providers::RetryProvider>
-            {
-                async fn provider(
-                    &self,
-                ) -> Result<$crate::providers::RetryProvider, $crate::error::Error> {
-                    let provider: $crate::providers::RetryProvider =
-                        $crate::providers::AbiProvider::_new(self.0.url.clone(), self.0.chain)
-                            .provider()
-                            .await?;
-
-                    Ok(provider)
+    fn unindex_entry(&self, address: Address, entry: &RegistryEntry<C>) {
+        let mut index = self.index();
+        let last_used = entry.last_used.load(Ordering::Relaxed);
+        index.recency.remove(&(last_used, address));
+        index.insertion.remove(&(entry.inserted_at, address));
+    }
+
+    /// Marks the entry as used now. The index lock is never held while a map
+    /// guard is acquired, so it may be taken while holding one.
+    fn touch(&self, address: Address, entry: &RegistryEntry<C>) {
+        let last_used = self.tick();
+        let previous = entry.last_used.swap(last_used, Ordering::Relaxed);
+        if self.config.capacity.is_some() {
+            let mut index = self.index();
+            index.recency.remove(&(previous, address));
+            index.recency.insert((last_used, address));
+        }
+    }
+
+    /// Removes expired entries, then evicts least recently used entries until
+    /// the registry is within capacity. No map guard may be held by the caller.
+    fn enforce_bounds(&self) {
+        let mut removed = self.remove_expired();
+        if let Some(capacity) = self.config.capacity {
+            while self.registry.len() > capacity {
+                let Some((last_used, lru)) = self.index().recency.pop_first() else {
+                    break;
+                };
+                let evicted = self.registry.remove_if(&lru, |_, entry| {
+                    entry.last_used.load(Ordering::Relaxed) == last_used
+                });
+                if let Some((_, entry)) = evicted {
+                    self.unindex_entry(lru, &entry);
+                    self.metrics.evictions.fetch_add(1, Ordering::Relaxed);
+                    removed.push(lru);
                 }
             }
+        }
+
+        self.forget(&removed);
+    }
+
+    fn remove_expired(&self) -> Vec<Address> {
+        let mut expired = vec![];
+        let Some(ttl) = self.config.ttl else {
+            return expired;
+        };
 
-            #[async_trait::async_trait]
-            impl $crate::providers::AbiProviderTrait<$crate::providers::MockProvider>
-                for [<$abi Registry>]<$crate::providers::MockProvider>
-            {
-                async fn provider(
-                    &self,
-                ) -> Result<$crate::providers::MockProvider, $crate::error::Error> {
-                    let provider: $crate::providers::MockProvider =
-                        $crate::providers::AbiProvider::mock().provider().await?;
-
-                    Ok(provider)
+        loop {
+            let oldest = {
+                let mut index = self.index();
+                match index.insertion.first() {
+                    Some((inserted_at, _)) if inserted_at.elapsed() > ttl => {
+                        index.insertion.pop_first()
+                    }
+                    _ => None,
                 }
+            };
+            let Some((inserted_at, address)) = oldest else {
+                break;
+            };
+
+            let removed = self
+                .registry
+                .remove_if(&address, |_, entry| entry.inserted_at == inserted_at);
+            if let Some((_, entry)) = removed {
+                self.unindex_entry(address, &entry);
+                expired.push(address);
             }
+        }
+        self.metrics
+            .expirations
+            .fetch_add(expired.len() as u64, Ordering::Relaxed);
 
-            impl<M> [<$abi Registry>]<M>
-            where
-                M: ::ethers::prelude::Middleware,
-            {
-                pub fn new(url: String, chain: $crate::chain::Chain) -> Self {
-                    let registry = $crate::registry::AbiRegistry::<$abi<M>>::new(url, chain);
-                    Self(registry)
-                }
+        expired
+    }
 
-                pub fn mock() -> Self {
-                    let registry = $crate::registry::AbiRegistry::<$abi<M>>::mock();
-                    Self(registry)
-                }
+    /// Drops the aliases and metadata of entries which are no longer registered.
+    fn forget(&self, addresses: &[Address]) {
+        if addresses.is_empty() {
+            return;
+        }
 
-                pub fn register(&self, provider: M, address: ::ethers::prelude::Address) -> $abi<M> {
-                    if !self.0.entry_exists(address) {
-                        let instance = $abi::new(address, provider.into());
-                        self.0.add_entry(address, instance)
-                    }
+        self.aliases
+            .retain(|_, address| !addresses.contains(address));
+        self.metadata
+            .retain(|address, _| !addresses.contains(address));
+    }
+}
 
-                    let clone_lock = std::sync::Arc::clone(&self.0.registry);
-                    let registry = clone_lock.read().expect("Registry RwLock poisoned!");
-                    let instance = registry.get(&address).unwrap().clone();
-                    drop(registry);
+impl<C> AbiRegistry<C>
+where
+    C: Clone,
+{
+    /// Counts a hit or a miss and marks the entry as recently used.
+    pub fn get_entry(&self, address: Address) -> Option<C> {
+        let entry = self
+            .registry
+            .get(&address)
+            .filter(|entry| !entry.is_expired(self.config.ttl))
+            .map(|entry| {
+                self.touch(address, &entry);
+                entry.contract.clone()
+            });
 
-                    instance
-                }
+        let counter = match entry {
+            Some(_) => &self.metrics.hits,
+            None => &self.metrics.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
 
-                pub fn chain(&self) -> Option<$crate::chain::Chain> {
-                    self.0.chain
-                }
-            }
+        entry
+    }
 
-            impl<M> $abi<M>
-            where
-                M: ::ethers::prelude::Middleware,
-            {
-                pub async fn get_logs<E>(
-                    &self,
-                    from_block: ::ethers::prelude::BlockNumber,
-                    to_block: ::ethers::prelude::BlockNumber,
-                ) -> Result<Vec<E>, $crate::error::Error>
-                where
-                    E: ethers::prelude::EthEvent + std::fmt::Debug,
-                {
-                    let res = self
-                        .event::<E>()
-                        .address(ethers::prelude::ValueOrArray::Value(self.address()))
-                        .from_block(from_block)
-                        .to_block(to_block)
-                        .query()
-                        .await?;
-
-                    Ok(res)
-                }
+    /// Returns the registered contract, or inserts the one built by `contract`
+    /// under a single lock so that concurrent callers observe one instance.
+    pub fn get_or_insert_with(&self, address: Address, contract: impl FnOnce() -> C) -> C {
+        let (contract, inserted) = match self.registry.entry(address) {
+            Entry::Occupied(entry) if !entry.get().is_expired(self.config.ttl) => {
+                self.touch(address, entry.get());
+                self.metrics.hits.fetch_add(1, Ordering::Relaxed);
+                (entry.get().contract.clone(), false)
+            }
+            Entry::Occupied(mut entry) => {
+                let contract = contract();
+                let previous = entry.insert(self.new_entry(contract.clone()));
+                self.unindex_entry(address, &previous);
+                self.index_entry(address, entry.get());
+                self.metrics.expirations.fetch_add(1, Ordering::Relaxed);
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                (contract, true)
             }
+            Entry::Vacant(entry) => {
+                let contract = contract();
+                let entry = entry.insert(self.new_entry(contract.clone()));
+                self.index_entry(address, &entry);
+                self.metrics.misses.fetch_add(1, Ordering::Relaxed);
+                (contract, true)
+            }
+        };
+        if inserted {
+            self.enforce_bounds();
         }
+
+        contract
+    }
+
+    pub fn get_entry_by_alias(&self, alias: &str) -> Option<C> {
+        self.get_entry(self.resolve_alias(alias)?)
+    }
+
+    pub fn entries(&self) -> Vec<(Address, C)> {
+        self.registry
+            .iter()
+            .filter(|entry| !entry.is_expired(self.config.ttl))
+            .map(|entry| (*entry.key(), entry.contract.clone()))
+            .collect()
+    }
+}
+/// Generates a contract registry and its `AbiProviderTrait` implementations
+/// for every transport, a multi-chain registry and helpers on the contract
+/// type. `abirpc!(Erc20Token)` generates `Erc20TokenRegistry<M>` and
+/// `Erc20TokenMultiChainRegistry<M>`.
+#[macro_export]
+macro_rules! abirpc {
+    ($abi:ident) => {
+        $crate::__abirpc!($crate, $abi);
     };
 }
 
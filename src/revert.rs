@@ -0,0 +1,191 @@
+use {
+    ethers::{
+        abi::{decode, ParamType, Token},
+        contract::{ContractRevert, EthError},
+        types::{Bytes, U256},
+    },
+    std::fmt,
+};
+
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Solidity `Panic(uint256)` codes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PanicCode {
+    Generic,
+    AssertionFailed,
+    ArithmeticOverflow,
+    DivisionByZero,
+    InvalidEnumValue,
+    InvalidStorageByteArray,
+    EmptyArrayPop,
+    ArrayOutOfBounds,
+    OutOfMemory,
+    InvalidFunction,
+    Unknown(U256),
+}
+
+impl From<U256> for PanicCode {
+    fn from(code: U256) -> Self {
+        if code > U256::from(u8::MAX) {
+            return PanicCode::Unknown(code);
+        }
+
+        match code.low_u64() {
+            0x00 => PanicCode::Generic,
+            0x01 => PanicCode::AssertionFailed,
+            0x11 => PanicCode::ArithmeticOverflow,
+            0x12 => PanicCode::DivisionByZero,
+            0x21 => PanicCode::InvalidEnumValue,
+            0x22 => PanicCode::InvalidStorageByteArray,
+            0x31 => PanicCode::EmptyArrayPop,
+            0x32 => PanicCode::ArrayOutOfBounds,
+            0x41 => PanicCode::OutOfMemory,
+            0x51 => PanicCode::InvalidFunction,
+            _ => PanicCode::Unknown(code),
+        }
+    }
+}
+
+impl PanicCode {
+    pub fn code(&self) -> U256 {
+        match self {
+            PanicCode::Generic => U256::from(0x00),
+            PanicCode::AssertionFailed => U256::from(0x01),
+            PanicCode::ArithmeticOverflow => U256::from(0x11),
+            PanicCode::DivisionByZero => U256::from(0x12),
+            PanicCode::InvalidEnumValue => U256::from(0x21),
+            PanicCode::InvalidStorageByteArray => U256::from(0x22),
+            PanicCode::EmptyArrayPop => U256::from(0x31),
+            PanicCode::ArrayOutOfBounds => U256::from(0x32),
+            PanicCode::OutOfMemory => U256::from(0x41),
+            PanicCode::InvalidFunction => U256::from(0x51),
+            PanicCode::Unknown(code) => *code,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PanicCode::Generic => "generic compiler inserted panic",
+            PanicCode::AssertionFailed => "assertion failed",
+            PanicCode::ArithmeticOverflow => "arithmetic overflow or underflow",
+            PanicCode::DivisionByZero => "division or modulo by zero",
+            PanicCode::InvalidEnumValue => "invalid enum value",
+            PanicCode::InvalidStorageByteArray => "incorrectly encoded storage byte array",
+            PanicCode::EmptyArrayPop => "pop on an empty array",
+            PanicCode::ArrayOutOfBounds => "array index out of bounds",
+            PanicCode::OutOfMemory => "too much memory allocated",
+            PanicCode::InvalidFunction => "call to an uninitialized internal function",
+            PanicCode::Unknown(_) => "unknown panic code",
+        }
+    }
+}
+
+impl fmt::Display for PanicCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#04x} ({})", self.code(), self.description())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum RevertReason {
+    Error(String),
+    Panic(PanicCode),
+    Custom([u8; 4]),
+    Unknown,
+    Empty,
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Error(reason) => write!(f, "Error({reason})"),
+            RevertReason::Panic(code) => write!(f, "Panic({code})"),
+            RevertReason::Custom(selector) => {
+                write!(
+                    f,
+                    "custom error 0x{}",
+                    rustc_hex::ToHex::to_hex::<String>(&selector[..])
+                )
+            }
+            RevertReason::Unknown => write!(f, "malformed revert data"),
+            RevertReason::Empty => write!(f, "no revert data"),
+        }
+    }
+}
+
+/// Raw revert data along with the decoded `Error(string)` or `Panic(uint256)`
+/// reason. Custom errors are decoded with [`Revert::decode_contract_revert`].
+///
+/// ```
+/// use ethers::{abi::{encode, Token}, types::{Bytes, U256}};
+/// use ethers_abirpc::prelude::*;
+///
+/// // Panic(uint256) selector followed by the arithmetic overflow code
+/// let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+/// data.extend(encode(&[Token::Uint(U256::from(0x11))]));
+///
+/// let err = Error::Revert(Revert::from(Bytes::from(data)));
+/// assert_eq!(
+///     err.to_string(),
+///     "Revert error: execution reverted: Panic(0x11 (arithmetic overflow or underflow))"
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Revert {
+    pub data: Bytes,
+    pub reason: RevertReason,
+}
+
+impl From<Bytes> for Revert {
+    fn from(data: Bytes) -> Self {
+        let reason = match data.get(..4) {
+            None if data.is_empty() => RevertReason::Empty,
+            None => RevertReason::Unknown,
+            Some(selector) => {
+                let selector: [u8; 4] = selector.try_into().expect("checked by len");
+                let reason = match selector {
+                    ERROR_SELECTOR => decode_single(&data[4..], ParamType::String)
+                        .and_then(Token::into_string)
+                        .map(RevertReason::Error),
+                    PANIC_SELECTOR => decode_single(&data[4..], ParamType::Uint(256))
+                        .and_then(Token::into_uint)
+                        .map(|code| RevertReason::Panic(PanicCode::from(code))),
+                    _ => None,
+                };
+                reason.unwrap_or(RevertReason::Custom(selector))
+            }
+        };
+
+        Self { data, reason }
+    }
+}
+
+impl Revert {
+    pub fn selector(&self) -> Option<[u8; 4]> {
+        self.data
+            .get(..4)
+            .map(|s| s.try_into().expect("checked by len"))
+    }
+
+    /// Decodes the revert data into an abigen generated `<Contract>Errors` enum.
+    pub fn decode_contract_revert<E: ContractRevert>(&self) -> Option<E> {
+        E::decode_with_selector(&self.data)
+    }
+
+    /// Decodes the revert data into a single abigen generated error type.
+    pub fn decode_revert<E: EthError>(&self) -> Option<E> {
+        E::decode_with_selector(&self.data)
+    }
+}
+
+impl fmt::Display for Revert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "execution reverted: {}", self.reason)
+    }
+}
+
+fn decode_single(data: &[u8], param: ParamType) -> Option<Token> {
+    decode(&[param], data).ok()?.pop()
+}
@@ -0,0 +1,140 @@
+use {
+    crate::{error::Error, pinned::pinned_block},
+    ethers::{
+        abi::Detokenize,
+        contract::ContractCall,
+        providers::Middleware,
+        types::{spoof, Address, BlockId, BlockNumber, Bytes, CallFrame, H256, U256, U64},
+    },
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub random: Option<H256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Overrides {
+    pub state: spoof::State,
+    pub block: Option<BlockOverrides>,
+}
+
+impl Overrides {
+    pub fn account(&mut self, address: Address) -> &mut spoof::Account {
+        self.state.account(address)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state == spoof::State::default() && self.block.is_none()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Simulation<D> {
+    pub output: D,
+    /// Gas used by the call, reported by `eth_createAccessList` or, with
+    /// overrides, a `debug_traceCall` call tracer. Nodes supporting neither
+    /// report the `eth_estimateGas` upper bound instead.
+    pub gas_used: U256,
+}
+
+/// Executes `call` via `eth_call` with the given state and block overrides.
+/// Without overrides the call and gas requests are sent through the
+/// middleware stack. The middleware API has no override parameters, so with
+/// overrides the requests are sent to the provider at the block pinned by a
+/// `BlockPinned` client, and from its default sender.
+pub async fn simulate_call<M, D>(
+    client: &M,
+    call: &ContractCall<M, D>,
+    overrides: &Overrides,
+) -> Result<Simulation<D>, Error>
+where
+    M: Middleware + 'static,
+    M::Inner: 'static,
+    D: Detokenize,
+{
+    let (output, gas_used) = match overrides.is_empty() {
+        true => {
+            let output = client
+                .call(&call.tx, call.block)
+                .await
+                .map_err(Error::from_middleware_error)?;
+            let gas_used = match client
+                .create_access_list(&call.tx, call.block)
+                .await
+                .map_err(Error::from_middleware_error)
+            {
+                Ok(access_list) => access_list.gas_used,
+                Err(e) if e.is_method_not_found() => client
+                    .estimate_gas(&call.tx, call.block)
+                    .await
+                    .map_err(Error::from_middleware_error)?,
+                Err(e) => return Err(e),
+            };
+            (output, gas_used)
+        }
+        false => {
+            let mut tx = call.tx.clone();
+            if tx.from().is_none() {
+                if let Some(from) = client.default_sender() {
+                    tx.set_from(from);
+                }
+            }
+            let block = call
+                .block
+                .or_else(|| pinned_block(client))
+                .unwrap_or(BlockId::Number(BlockNumber::Latest));
+
+            let mut params = vec![
+                serde_json::to_value(&tx)?,
+                serde_json::to_value(block)?,
+                serde_json::to_value(&overrides.state)?,
+            ];
+            let mut config = json!({
+                "tracer": "callTracer",
+                "stateOverrides": &overrides.state,
+            });
+            if let Some(block_overrides) = &overrides.block {
+                params.push(serde_json::to_value(block_overrides)?);
+                config["blockOverrides"] = serde_json::to_value(block_overrides)?;
+            }
+            let trace_params = [params[0].clone(), params[1].clone(), config];
+
+            let provider = client.provider();
+            let output: Bytes = provider.request("eth_call", &params).await?;
+            let gas_used = match provider
+                .request::<_, CallFrame>("debug_traceCall", &trace_params)
+                .await
+                .map_err(Error::from)
+            {
+                Ok(frame) => frame.gas_used,
+                Err(e) if e.is_method_not_found() => {
+                    provider.request("eth_estimateGas", &params).await?
+                }
+                Err(e) => return Err(e),
+            };
+            (output, gas_used)
+        }
+    };
+
+    let tokens = call.function.decode_output(output.as_ref())?;
+    let output = D::from_tokens(tokens)?;
+
+    Ok(Simulation { output, gas_used })
+}
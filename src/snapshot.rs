@@ -0,0 +1,87 @@
+use {
+    crate::{error::Error, proxy::ProxyKind},
+    ethers::types::Address,
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, fs, path::Path},
+};
+
+/// Metadata recorded alongside a registry entry.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment_block: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<Address>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, String>,
+}
+
+impl EntryMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub address: Address,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "EntryMetadata::is_empty")]
+    pub metadata: EntryMetadata,
+}
+
+/// Serializable view of a registry. `abi` is the name of the contract binding
+/// passed to `abirpc!` and is checked when the snapshot is restored.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    pub abi: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl RegistrySnapshot {
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_toml(&self) -> Result<String, Error> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    pub fn from_toml(toml: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Writes the snapshot, choosing the format from the file extension.
+    pub fn to_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let contents = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => self.to_json()?,
+            Some("toml") => self.to_toml()?,
+            _ => return Err(Error::UnsupportedFormat(path.display().to_string())),
+        };
+
+        Ok(fs::write(path, contents)?)
+    }
+
+    /// Reads a snapshot, choosing the format from the file extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            _ => Err(Error::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
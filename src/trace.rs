@@ -0,0 +1,318 @@
+use {
+    crate::{
+        decode::{selector_function, TxDecoder},
+        dynamic::{outputs_to_json, param_name, token_to_json},
+        error::Error,
+        revert::{Revert, RevertReason},
+    },
+    ethers::{
+        abi::{Abi, Token},
+        contract::BaseContract,
+        providers::Middleware,
+        types::{Action, Address, Bytes, CallFrame, CallType, Res, Trace, H256, U256},
+        utils::hex,
+    },
+    serde::Serialize,
+    serde_json::{json, Map, Value},
+    std::{fmt, sync::Arc},
+};
+
+/// Frame of a transaction call tree. Frames targeting registered addresses are
+/// decoded, other frames keep the raw selector and output.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct DecodedFrame {
+    /// Call type, e.g. `CALL`, `STATICCALL`, `DELEGATECALL` or `CREATE`.
+    pub kind: String,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub gas_used: U256,
+    pub contract: Option<String>,
+    pub function: Option<String>,
+    pub selector: Option<String>,
+    pub args: Option<Map<String, Value>>,
+    pub output: Option<Value>,
+    pub error: Option<String>,
+    /// Decoded revert reason, including custom errors in the target ABI.
+    pub revert: Option<String>,
+    pub calls: Vec<DecodedFrame>,
+}
+
+struct RawFrame {
+    kind: String,
+    from: Address,
+    to: Option<Address>,
+    value: U256,
+    gas_used: U256,
+    input: Bytes,
+    output: Option<Bytes>,
+    error: Option<String>,
+}
+
+impl TxDecoder {
+    /// Fetches a `debug_traceTransaction` call tree using the geth `callTracer`.
+    pub async fn debug_trace_transaction<M>(
+        &self,
+        client: &M,
+        hash: H256,
+    ) -> Result<DecodedFrame, Error>
+    where
+        M: Middleware,
+    {
+        let params = (hash, json!({ "tracer": "callTracer" }));
+        let frame: CallFrame = client
+            .provider()
+            .request("debug_traceTransaction", params)
+            .await?;
+
+        Ok(self.decode_call_frame(&frame))
+    }
+
+    /// Fetches a parity style `trace_transaction` call tree. These traces do not
+    /// include revert data, so only the error message is reported.
+    pub async fn trace_transaction<M>(&self, client: &M, hash: H256) -> Result<DecodedFrame, Error>
+    where
+        M: Middleware,
+    {
+        let traces = client
+            .trace_transaction(hash)
+            .await
+            .map_err(Error::from_middleware_error)?;
+
+        self.decode_traces(&traces)
+            .ok_or(Error::TransactionNotFound(hash))
+    }
+
+    pub fn decode_call_frame(&self, frame: &CallFrame) -> DecodedFrame {
+        let raw = RawFrame {
+            kind: frame.typ.to_uppercase(),
+            from: frame.from,
+            to: frame.to.as_ref().and_then(|to| to.as_address().copied()),
+            value: frame.value.unwrap_or_default(),
+            gas_used: frame.gas_used,
+            input: frame.input.clone(),
+            output: frame.output.clone(),
+            error: frame.error.clone(),
+        };
+        let calls = frame
+            .calls
+            .iter()
+            .flatten()
+            .map(|call| self.decode_call_frame(call))
+            .collect();
+
+        self.decode_frame(raw, calls)
+    }
+
+    /// Builds the call tree from flat parity traces using their trace addresses.
+    pub fn decode_traces(&self, traces: &[Trace]) -> Option<DecodedFrame> {
+        let root = traces.iter().find(|trace| trace.trace_address.is_empty())?;
+
+        Some(self.decode_trace(root, traces))
+    }
+
+    fn decode_trace(&self, trace: &Trace, traces: &[Trace]) -> DecodedFrame {
+        let depth = trace.trace_address.len();
+        let mut children: Vec<&Trace> = traces
+            .iter()
+            .filter(|child| {
+                child.trace_address.len() == depth + 1
+                    && child.trace_address.starts_with(&trace.trace_address)
+            })
+            .collect();
+        children.sort_by(|a, b| a.trace_address.cmp(&b.trace_address));
+        let calls = children
+            .into_iter()
+            .map(|child| self.decode_trace(child, traces))
+            .collect();
+
+        let (gas_used, output, created) = match &trace.result {
+            Some(Res::Call(result)) => (result.gas_used, Some(result.output.clone()), None),
+            Some(Res::Create(result)) => (result.gas_used, None, Some(result.address)),
+            _ => (U256::zero(), None, None),
+        };
+        let raw = match &trace.action {
+            Action::Call(call) => RawFrame {
+                kind: call_kind(&call.call_type).to_string(),
+                from: call.from,
+                to: Some(call.to),
+                value: call.value,
+                gas_used,
+                input: call.input.clone(),
+                output,
+                error: trace.error.clone(),
+            },
+            Action::Create(create) => RawFrame {
+                kind: String::from("CREATE"),
+                from: create.from,
+                to: created,
+                value: create.value,
+                gas_used,
+                input: Bytes::default(),
+                output: None,
+                error: trace.error.clone(),
+            },
+            Action::Suicide(suicide) => RawFrame {
+                kind: String::from("SELFDESTRUCT"),
+                from: suicide.address,
+                to: Some(suicide.refund_address),
+                value: suicide.balance,
+                gas_used,
+                input: Bytes::default(),
+                output: None,
+                error: trace.error.clone(),
+            },
+            Action::Reward(reward) => RawFrame {
+                kind: String::from("REWARD"),
+                from: reward.author,
+                to: None,
+                value: reward.value,
+                gas_used,
+                input: Bytes::default(),
+                output: None,
+                error: trace.error.clone(),
+            },
+        };
+
+        self.decode_frame(raw, calls)
+    }
+
+    fn decode_frame(&self, raw: RawFrame, calls: Vec<DecodedFrame>) -> DecodedFrame {
+        let entry = raw.to.and_then(|to| self.get(to));
+        let base = entry.as_ref().map(|(_, base)| Arc::clone(base));
+        let abi = base.as_deref().map(BaseContract::abi);
+        let selector = raw.input.get(..4);
+        let function = base
+            .as_deref()
+            .and_then(|base| selector_function(base, &raw.input));
+
+        let args = function.and_then(|function| {
+            let tokens = function.decode_input(&raw.input[4..]).ok()?;
+            let args = tokens
+                .iter()
+                .zip(&function.inputs)
+                .enumerate()
+                .map(|(i, (token, param))| (param_name(&param.name, i), token_to_json(token)))
+                .collect();
+            Some(args)
+        });
+
+        let output = raw.output.as_ref().filter(|output| !output.is_empty());
+        let revert = match (&raw.error, output) {
+            (Some(_), Some(output)) => Some(revert_reason(abi, output)),
+            _ => None,
+        };
+        let output = match (&raw.error, output) {
+            (None, Some(output)) => {
+                let decoded = function.and_then(|function| {
+                    let tokens = function.decode_output(output).ok()?;
+                    Some(outputs_to_json(&function.outputs, tokens))
+                });
+                Some(decoded.unwrap_or_else(|| Value::String(output.to_string())))
+            }
+            _ => None,
+        };
+
+        DecodedFrame {
+            kind: raw.kind,
+            from: raw.from,
+            to: raw.to,
+            value: raw.value,
+            gas_used: raw.gas_used,
+            contract: entry.map(|(contract, _)| contract),
+            function: function.map(|function| function.name.clone()),
+            selector: selector.map(|selector| format!("0x{}", hex::encode(selector))),
+            args,
+            output,
+            error: raw.error,
+            revert,
+            calls,
+        }
+    }
+}
+
+fn call_kind(call_type: &CallType) -> &'static str {
+    match call_type {
+        CallType::None | CallType::Call => "CALL",
+        CallType::CallCode => "CALLCODE",
+        CallType::DelegateCall => "DELEGATECALL",
+        CallType::StaticCall => "STATICCALL",
+    }
+}
+
+/// Custom errors are decoded against the target ABI when it is registered.
+fn revert_reason(abi: Option<&Abi>, data: &Bytes) -> String {
+    let revert = Revert::from(data.clone());
+    let RevertReason::Custom(selector) = revert.reason else {
+        return revert.reason.to_string();
+    };
+
+    let error = abi.and_then(|abi| {
+        abi.errors()
+            .find(|error| error.signature()[..4] == selector)
+    });
+    let tokens = error.and_then(|error| error.decode(&data[4..]).ok());
+    match error.zip(tokens) {
+        Some((error, tokens)) => format!("{}({})", error.name, format_tokens(&tokens)),
+        None => revert.reason.to_string(),
+    }
+}
+
+fn format_tokens(tokens: &[Token]) -> String {
+    let values: Vec<String> = tokens
+        .iter()
+        .map(|token| token_to_json(token).to_string())
+        .collect();
+    values.join(", ")
+}
+
+impl DecodedFrame {
+    fn fmt_frame(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:indent$}{} ", "", self.kind, indent = depth * 2)?;
+        match (&self.contract, self.to) {
+            (Some(contract), Some(to)) => write!(f, "{contract}({to:?})")?,
+            (None, Some(to)) => write!(f, "{to:?}")?,
+            (_, None) => write!(f, "{:?}", self.from)?,
+        }
+
+        match (&self.function, &self.selector) {
+            (Some(function), _) => {
+                let args: Vec<String> = self
+                    .args
+                    .iter()
+                    .flatten()
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect();
+                write!(f, ".{function}({})", args.join(", "))?;
+            }
+            (None, Some(selector)) => write!(f, "::{selector}")?,
+            (None, None) => {}
+        }
+        if !self.value.is_zero() {
+            write!(f, " {{value: {}}}", self.value)?;
+        }
+        if let Some(output) = &self.output {
+            write!(f, " -> {output}")?;
+        }
+        if let Some(error) = &self.error {
+            write!(f, " !! {error}")?;
+            if let Some(revert) = &self.revert {
+                write!(f, ": {revert}")?;
+            }
+        }
+        writeln!(f)?;
+
+        for call in &self.calls {
+            call.fmt_frame(f, depth + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders the call tree, one frame per line indented by depth.
+impl fmt::Display for DecodedFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_frame(f, 0)
+    }
+}
@@ -0,0 +1,159 @@
+use {
+    crate::error::Error,
+    ethers::{
+        abi::{decode, encode, Abi, Event, Function, ParamType, Token},
+        providers::Middleware,
+        types::{Address, Bytes, TransactionRequest},
+    },
+};
+
+// supportsInterface(bytes4)
+const SUPPORTS_INTERFACE_SELECTOR: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+const PUSH1: u8 = 0x60;
+
+/// Checks performed by `register_verified`. Contract code is always required
+/// to be non-empty.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Verification {
+    /// ERC-165 interface ids which must be reported as supported.
+    pub interfaces: Vec<[u8; 4]>,
+    /// Function selectors which must appear in the contract's dispatcher.
+    pub selectors: Vec<[u8; 4]>,
+    /// Require every function selector of the ABI to appear in the dispatcher.
+    pub abi_selectors: bool,
+}
+
+impl Verification {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn interface(mut self, interface_id: [u8; 4]) -> Self {
+        self.interfaces.push(interface_id);
+        self
+    }
+
+    pub fn selectors(mut self, selectors: impl IntoIterator<Item = [u8; 4]>) -> Self {
+        self.selectors.extend(selectors);
+        self
+    }
+
+    pub fn abi_selectors(mut self) -> Self {
+        self.abi_selectors = true;
+        self
+    }
+}
+
+/// Verifies the code deployed at `address` against `verification`.
+///
+/// Selector probing scans the runtime bytecode for the `PUSH` of each selector
+/// made by the function dispatcher. Proxies only contain the dispatcher of the
+/// proxy itself, so selector checks should be made against the implementation.
+pub async fn verify_contract<M: Middleware>(
+    client: &M,
+    address: Address,
+    abi: &Abi,
+    verification: &Verification,
+) -> Result<(), Error> {
+    let code = client
+        .get_code(address, None)
+        .await
+        .map_err(Error::from_middleware_error)?;
+    if code.is_empty() {
+        return Err(Error::NoCode { address });
+    }
+
+    for interface_id in &verification.interfaces {
+        if !supports_interface(client, address, *interface_id).await? {
+            return Err(Error::InterfaceNotSupported {
+                address,
+                interface_id: format!(
+                    "0x{}",
+                    rustc_hex::ToHex::to_hex::<String>(&interface_id[..])
+                ),
+            });
+        }
+    }
+
+    let mut missing: Vec<String> = verification
+        .selectors
+        .iter()
+        .filter(|selector| !dispatches(&code, selector))
+        .map(|selector| format!("0x{}", rustc_hex::ToHex::to_hex::<String>(&selector[..])))
+        .collect();
+    if verification.abi_selectors {
+        missing.extend(
+            abi.functions()
+                .filter(|function| !dispatches(&code, &function.short_signature()))
+                .map(signature),
+        );
+    }
+    if !missing.is_empty() {
+        return Err(Error::MissingSelectors {
+            address,
+            selectors: missing,
+        });
+    }
+
+    Ok(())
+}
+
+/// Calls ERC-165 `supportsInterface`. Reverts and malformed return data are
+/// treated as the interface not being supported.
+pub async fn supports_interface<M: Middleware>(
+    client: &M,
+    address: Address,
+    interface_id: [u8; 4],
+) -> Result<bool, Error> {
+    let mut data = SUPPORTS_INTERFACE_SELECTOR.to_vec();
+    data.extend(encode(&[Token::FixedBytes(interface_id.to_vec())]));
+    let tx = TransactionRequest::new()
+        .to(address)
+        .data(Bytes::from(data));
+
+    let output = match client.call(&tx.into(), None).await {
+        Ok(output) => output,
+        Err(e) => match Error::from_middleware_error(e) {
+            Error::Revert(_) => return Ok(false),
+            e => return Err(e),
+        },
+    };
+
+    let supported = decode(&[ParamType::Bool], &output)
+        .ok()
+        .and_then(|mut tokens| tokens.pop())
+        .and_then(Token::into_bool)
+        .unwrap_or(false);
+
+    Ok(supported)
+}
+
+pub(crate) fn signature(function: &Function) -> String {
+    format_signature(
+        &function.name,
+        function.inputs.iter().map(|param| &param.kind),
+    )
+}
+
+pub(crate) fn event_signature(event: &Event) -> String {
+    format_signature(&event.name, event.inputs.iter().map(|param| &param.kind))
+}
+
+fn format_signature<'a>(name: &str, kinds: impl Iterator<Item = &'a ParamType>) -> String {
+    let inputs: Vec<String> = kinds.map(|kind| kind.to_string()).collect();
+
+    format!("{name}({})", inputs.join(","))
+}
+
+/// Selectors with leading zero bytes are pushed with a shorter `PUSH`.
+fn dispatches(code: &[u8], selector: &[u8; 4]) -> bool {
+    let trimmed = match selector.iter().position(|byte| *byte != 0) {
+        Some(start) => &selector[start..],
+        None => &selector[3..],
+    };
+
+    let mut pattern = vec![PUSH1 + trimmed.len() as u8 - 1];
+    pattern.extend_from_slice(trimmed);
+
+    code.windows(pattern.len()).any(|window| window == pattern)
+}
@@ -0,0 +1,61 @@
+use ethers::{
+    abi::{encode, Token},
+    types::{Bytes, H256},
+    utils::get_create2_address,
+};
+use ethers_abirpc::prelude::*;
+
+mod plain {
+    ethers_abirpc::abirpc_dir!("./tests/abi");
+}
+
+mod artifacts {
+    ethers_abirpc::abirpc_dir!("./tests/artifacts", bytecode = true);
+}
+
+mod selected {
+    ethers_abirpc::abirpc_dir!("./tests/artifacts", contracts(Counter));
+}
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+
+#[tokio::test]
+async fn test_abi_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = plain::Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(18))]));
+    provider.as_ref().push::<Bytes, _>(decimals)?;
+    assert_eq!(instance.decimals().call().await?, 18);
+
+    assert!(plain::Erc721TokenRegistry::<MockProvider>::mock().is_empty());
+    assert!(plain::VaultRegistry::<MockProvider>::mock().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_artifact_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let bytecode = "0x600a600c600039600a6000f3602a60005260206000f3".parse::<Bytes>()?;
+    assert_eq!(artifacts::COUNTER_BYTECODE.clone(), bytecode); // Foundry
+    assert_eq!(artifacts::GREETER_BYTECODE.clone(), bytecode); // Hardhat
+
+    let registry = artifacts::CounterRegistry::<MockProvider>::mock();
+    let salt = H256::repeat_byte(0x01);
+    let address = registry.create2_address(artifacts::COUNTER_BYTECODE.clone(), (), salt)?;
+    assert_eq!(
+        address,
+        get_create2_address(CREATE2_DEPLOYER, salt, bytecode)
+    );
+
+    let registry = selected::CounterRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+
+    let number = Bytes::from(encode(&[Token::Uint(U256::from(42))]));
+    provider.as_ref().push::<Bytes, _>(number)?;
+    assert_eq!(instance.number().call().await?, U256::from(42));
+
+    Ok(())
+}
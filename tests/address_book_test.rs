@@ -0,0 +1,91 @@
+use ethers::providers::Provider;
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_CHAIN: Chain = Chain::Id(1);
+const TEST_WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const TEST_USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+const TEST_OP_USDC: &str = "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85";
+
+#[tokio::test]
+async fn test_alias() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let weth = address_from!(TEST_WETH)?;
+
+    let instance = registry.register_as(provider.clone(), "WETH", weth);
+    registry.alias("wrapped-ether", weth);
+
+    assert_eq!(registry.resolve("WETH"), Some(weth));
+    assert_eq!(
+        registry.get_by_alias("wrapped-ether").map(|c| c.address()),
+        Some(instance.address())
+    );
+    assert_eq!(registry.aliases_of(weth), vec!["WETH", "wrapped-ether"]);
+    assert!(registry.get_by_alias("USDC").is_none());
+
+    assert_eq!(registry.unalias("wrapped-ether"), Some(weth));
+    assert_eq!(registry.aliases(), vec![("WETH".to_string(), weth)]);
+
+    registry.unregister(weth);
+    assert!(registry.resolve("WETH").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_address_book_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let toml = AddressBook::from_file("./tests/config/addresses.toml")?;
+    let json = AddressBook::from_file("./tests/config/addresses.json")?;
+    assert_eq!(toml, json);
+
+    assert_eq!(
+        toml.get(TEST_CHAIN, "USDC"),
+        Some(address_from!(TEST_USDC)?)
+    );
+    assert_eq!(
+        toml.get(Chain::from(NamedChain::Optimism), "USDC"),
+        Some(address_from!(TEST_OP_USDC)?)
+    );
+    assert!(toml.get(Chain::Id(137), "USDC").is_none());
+
+    let err =
+        AddressBook::from_toml(&format!("[not-a-chain]\nUSDC = \"{TEST_USDC}\"")).unwrap_err();
+    assert!(matches!(err, Error::InvalidChain(_)));
+
+    let err = AddressBook::from_file("./tests/config/addresses.yaml").unwrap_err();
+    assert!(matches!(err, Error::IoError(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_address_book() -> Result<(), Box<dyn std::error::Error>> {
+    let book = AddressBook::from_file("./tests/config/addresses.toml")?;
+    let provider: MockProvider = Provider::new(ethers::providers::MockProvider::new());
+
+    let registry = Erc20TokenRegistry::<MockProvider>::new(String::new(), TEST_CHAIN);
+    let instances = registry.register_address_book(provider.clone(), &book)?;
+
+    assert_eq!(instances.len(), 2);
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.resolve("WETH"), Some(address_from!(TEST_WETH)?));
+    assert_eq!(
+        registry.get_by_alias("USDC").map(|c| c.address()),
+        Some(address_from!(TEST_USDC)?)
+    );
+
+    let registry = Erc20TokenRegistry::<MockProvider>::new(String::new(), Chain::Id(137));
+    let err = registry
+        .register_address_book(provider.clone(), &book)
+        .unwrap_err();
+    assert!(matches!(err, Error::UnknownChain { chain_id: 137 }));
+
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let err = registry.register_address_book(provider, &book).unwrap_err();
+    assert!(matches!(err, Error::MissingChain));
+
+    Ok(())
+}
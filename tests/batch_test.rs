@@ -0,0 +1,95 @@
+use {
+    common::{method, result, serve, Requests},
+    ethers_abirpc::prelude::*,
+    serde_json::{json, Value},
+};
+
+mod common;
+
+fn respond(request: &Value) -> Value {
+    match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_blockNumber" => result(json!("0x10")),
+        "eth_getBalance" => result(json!("0x64")),
+        _ => result(Value::Null),
+    }
+}
+
+/// Number of requests in each batch received.
+fn batch_sizes(requests: &Requests) -> Vec<usize> {
+    let requests = requests.lock().map(|r| r.clone()).unwrap_or_default();
+    requests
+        .iter()
+        .map(|body| body.as_array().map_or(1, Vec::len))
+        .collect()
+}
+
+fn batch_chain(max_batch_size: usize) -> Chain {
+    Chain::ChainConfig(ChainConfig {
+        chain_id: 1,
+        batch_client_config: BatchClientConfig {
+            max_batch_size,
+            batch_window_ms: 50,
+        },
+        ..ChainConfig::default()
+    })
+}
+
+#[tokio::test]
+async fn test_batch_coalesces_requests() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(respond).await?;
+    let provider: BatchProvider = AbiProvider::new(url, batch_chain(10)).provider().await?;
+
+    let address = address_from!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")?;
+    let (balance, block_number, chain_id) = tokio::join!(
+        provider.get_balance(address, None),
+        provider.get_block_number(),
+        provider.get_chainid(),
+    );
+
+    assert_eq!(balance?, U256::from(100));
+    assert_eq!(block_number?.as_u64(), 16);
+    assert_eq!(chain_id?, U256::from(1));
+    assert_eq!(batch_sizes(&requests), vec![3]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_max_batch_size() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(respond).await?;
+    let provider: BatchProvider = AbiProvider::new(url, batch_chain(2)).provider().await?;
+
+    let (a, b, c, d) = tokio::join!(
+        provider.get_block_number(),
+        provider.get_block_number(),
+        provider.get_block_number(),
+        provider.get_block_number(),
+    );
+    for res in [a, b, c, d] {
+        assert_eq!(res?.as_u64(), 16);
+    }
+
+    assert_eq!(batch_sizes(&requests), vec![2, 2]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_assert_chain_id() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let chain = Chain::ChainConfig(ChainConfig {
+        chain_id: 10,
+        assert_chain_id: true,
+        ..ChainConfig::default()
+    });
+
+    let provider: Result<BatchProvider, _> = AbiProvider::new(url, chain).provider().await;
+
+    assert!(matches!(
+        provider,
+        Err(Error::ChainIdMismatch { expected: 10, actual }) if actual == U256::from(1)
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use ethers_abirpc::{build::RegistryBuilder, prelude::*};
+use std::{env, fs};
+
+#[tokio::test]
+async fn test_registry_builder() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = env::temp_dir().join(format!("abirpc-build-{}", std::process::id()));
+
+    let bindings = RegistryBuilder::new()
+        .dir("./tests/abi")
+        .contract("Counter", "./tests/artifacts/out/Counter.sol/Counter.json")
+        .bytecode(true)
+        .generate()?;
+    let names = bindings
+        .contracts()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Counter", "Erc20Token", "Erc721Token", "Vault"]);
+
+    let counter = bindings.module("counter").ok_or("missing counter module")?;
+    assert!(counter.contains("pub static COUNTER_BYTECODE"));
+    assert!(counter.contains("pub struct CounterRegistry<M>"));
+    assert!(counter.contains("impl<M> ::ethers_abirpc::contract::AbiContract<M> for Counter<M>"));
+    assert!(!counter.contains("abirpc!("));
+
+    bindings.write_to_module(&out_dir)?;
+    let root = fs::read_to_string(out_dir.join("mod.rs"))?;
+    assert!(root.contains("pub mod erc_20_token;"));
+    assert!(root.contains("pub use counter::{Counter, CounterRegistry"));
+    assert!(root.contains("COUNTER_BYTECODE}"));
+    assert!(!root.contains("VAULT_BYTECODE"));
+    assert!(out_dir.join("erc_20_token.rs").exists());
+
+    // The generated module tree compiles when included
+    let main = out_dir.join("main.rs");
+    fs::write(
+        &main,
+        format!(
+            "#[allow(unused_imports)]\nmod contracts {{\n    include!({:?});\n}}\n\n\
+             fn main() {{\n    \
+                 let _ = contracts::CounterRegistry::<ethers::providers::Provider<ethers::providers::Http>>::new;\n    \
+                 let _ = &*contracts::COUNTER_BYTECODE;\n    \
+                 let _ = &*contracts::VAULT_ABI;\n\
+             }}\n",
+            out_dir.canonicalize()?.join("mod.rs").display().to_string()
+        ),
+    )?;
+    trybuild::TestCases::new().pass(&main);
+    fs::remove_dir_all(&out_dir)?;
+
+    // Contracts whose names map to the same module are rejected
+    let result = RegistryBuilder::new()
+        .contract("Vault", "./tests/abi/Vault.json")
+        .contract("VAULT", "./tests/abi/Erc20Token.json")
+        .generate();
+    assert!(
+        matches!(result, Err(Error::DuplicateModule { first, second, .. })
+        if first.ends_with("Vault.json") && second.ends_with("Erc20Token.json"))
+    );
+
+    // Duplicate contract names are rejected
+    let result = RegistryBuilder::new()
+        .dir("./tests/artifacts")
+        .contract("Greeter", "./tests/abi/Vault.json")
+        .generate();
+    assert!(matches!(result, Err(Error::DuplicateContract { name, .. }) if name == "Greeter"));
+
+    // Named contracts must be an ABI or artifact
+    let result = RegistryBuilder::new()
+        .contract(
+            "Greeter",
+            "./tests/artifacts/contracts/Greeter.sol/Greeter.dbg.json",
+        )
+        .generate();
+    assert!(matches!(result, Err(Error::InvalidArtifact { .. })));
+
+    // Bytecode with library placeholders cannot be embedded
+    fs::create_dir_all(&out_dir)?;
+    let linked = out_dir.join("Linked.json");
+    fs::write(
+        &linked,
+        r#"{ "abi": [], "bytecode": "0x6080__$1234567890$__6080" }"#,
+    )?;
+    let result = RegistryBuilder::new()
+        .contract("Linked", &linked)
+        .bytecode(true)
+        .generate();
+    assert!(matches!(result, Err(Error::UnlinkedBytecode { path }) if path == linked));
+    fs::remove_dir_all(&out_dir)?;
+
+    // Outside of a build script there is no OUT_DIR to write to
+    let result = RegistryBuilder::new().write_to_out_dir("contracts");
+    assert!(matches!(result, Err(Error::MissingOutDir)));
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use {
+    common::{block_range, error, method, result, serve},
+    ethers_abirpc::{capabilities::probe_method, prelude::*},
+    serde_json::{json, Value},
+};
+
+mod common;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const HEAD: u64 = 20_000;
+const MAX_LOGS_RANGE: u64 = 2_000;
+
+/// Node without debug or trace namespaces and without archive state.
+fn respond(request: &Value) -> Value {
+    match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_getBlockByNumber" => result(json!({
+            "hash": format!("0x{}", "11".repeat(32)),
+            "parentHash": format!("0x{}", "00".repeat(32)),
+            "number": format!("{HEAD:#x}"),
+            "timestamp": "0x0",
+            "baseFeePerGas": "0x3b9aca00",
+            "transactions": [],
+            "uncles": [],
+        })),
+        "eth_getBalance" => error(-32000, "missing trie node"),
+        "eth_getLogs" if block_range(request) > MAX_LOGS_RANGE => {
+            error(-32000, "block range too large")
+        }
+        "eth_getLogs" => result(json!([])),
+        "debug_traceTransaction" => error(-32000, "transaction not found"),
+        "eth_rateLimited" => error(-32005, "daily request count exceeded"),
+        "eth_disabled" => error(-32004, "disabled"),
+        "eth_failing" => error(-32603, "internal error"),
+        _ => error(-32601, "Method not found"),
+    }
+}
+
+fn probing_chain() -> Chain {
+    Chain::ChainConfig(ChainConfig {
+        chain_id: 1,
+        assert_chain_id: true,
+        probe_capabilities: true,
+        ..ChainConfig::default()
+    })
+}
+
+#[tokio::test]
+async fn test_probe_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let abi_provider = AbiProvider::new(url, probing_chain());
+    let _provider: HttpProvider = abi_provider.provider().await?;
+
+    assert_eq!(
+        abi_provider.capabilities(),
+        Some(Capabilities {
+            archive: false,
+            max_logs_range: Some(MAX_LOGS_RANGE),
+            batch: true,
+            subscriptions: false,
+            debug: true,
+            trace: false,
+            eip1559: true,
+        })
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_probe_disabled() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let abi_provider = AbiProvider::new(url, Chain::from_id(1));
+    let _provider: HttpProvider = abi_provider.provider().await?;
+
+    assert_eq!(abi_provider.capabilities(), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let registry = Erc20TokenRegistry::<RetryProvider>::new(url.clone(), probing_chain());
+    assert_eq!(registry.capabilities(), None);
+
+    let provider = registry.provider().await?;
+    let capabilities = registry.capabilities().ok_or("capabilities not recorded")?;
+    assert_eq!(capabilities.max_logs_range, Some(MAX_LOGS_RANGE));
+
+    let pinned = registry.at_block(provider, 1u64);
+    assert_eq!(pinned.capabilities(), Some(capabilities));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_capabilities_per_provider() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let registry = Erc20TokenRegistry::<HttpProvider>::new(url.clone(), probing_chain());
+    let _provider = registry.provider().await?;
+    assert!(registry.capabilities().is_some());
+
+    let other = Erc20TokenRegistry::<HttpProvider>::new(url.clone(), Chain::from_id(1));
+    let _provider = other.provider().await?;
+    assert_eq!(other.capabilities(), None);
+
+    let abi_provider = AbiProvider::new(url, Chain::from_id(1));
+    let _provider: BatchProvider = abi_provider.provider().await?;
+    assert_eq!(abi_provider.capabilities(), None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_probe_method_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+    let provider: HttpProvider = AbiProvider::new(url, Chain::from_id(1)).provider().await?;
+
+    let probe = |method| probe_method(&provider, method, json!([]));
+    assert!(probe("debug_traceTransaction").await?);
+    assert!(!probe("trace_transaction").await?);
+    assert!(!probe("eth_disabled").await?);
+    assert!(probe("eth_rateLimited").await.is_err());
+    assert!(probe("eth_failing").await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connected_registry_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(respond).await?;
+
+    let registry = Erc20TokenMultiChainRegistry::<HttpProvider>::new();
+    registry.add_chain(url.clone(), probing_chain()).await?;
+    let capabilities = registry
+        .capabilities(Chain::Id(1))
+        .ok_or("capabilities not recorded")?;
+    assert_eq!(capabilities.max_logs_range, Some(MAX_LOGS_RANGE));
+
+    let contracts = ContractRegistry::<HttpProvider>::connect(url, probing_chain()).await?;
+    assert_eq!(contracts.capabilities(), Some(capabilities));
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use ethers::types::Address;
+use ethers_abirpc::prelude::*;
+use std::time::Duration;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+fn address(n: u64) -> Address {
+    Address::from_low_u64_be(n)
+}
+
+#[tokio::test]
+async fn test_lru_eviction() -> Result<(), Box<dyn std::error::Error>> {
+    let config = RegistryConfig {
+        capacity: Some(2),
+        ..RegistryConfig::default()
+    };
+    let registry = Erc20TokenRegistry::<MockProvider>::mock().with_config(config);
+    let provider = registry.provider().await?;
+
+    registry.register_as(provider.clone(), "first", address(1));
+    registry.register(provider.clone(), address(2));
+    assert!(registry.get(address(1)).is_some());
+
+    // address(2) is the least recently used entry
+    registry.register(provider.clone(), address(3));
+    assert_eq!(registry.len(), 2);
+    assert!(registry.contains(address(1)));
+    assert!(!registry.contains(address(2)));
+    assert!(registry.contains(address(3)));
+
+    registry.register(provider.clone(), address(4));
+    assert!(!registry.contains(address(1)));
+    assert!(registry.resolve("first").is_none());
+
+    let metrics = registry.metrics();
+    assert_eq!(metrics.evictions, 2);
+    assert_eq!(metrics.hits, 1);
+    assert_eq!(metrics.misses, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lru_eviction_past_capacity() -> Result<(), Box<dyn std::error::Error>> {
+    let config = RegistryConfig {
+        capacity: Some(10),
+        ..RegistryConfig::default()
+    };
+    let registry = Erc20TokenRegistry::<MockProvider>::mock().with_config(config);
+    let provider = registry.provider().await?;
+
+    for n in 1..=200 {
+        registry.register(provider.clone(), address(n));
+        // Keep the first entries in use while the rest pass through
+        for kept in 1..=3 {
+            if n > kept {
+                assert!(registry.get(address(kept)).is_some());
+            }
+        }
+    }
+
+    let mut addresses = registry.addresses();
+    addresses.sort();
+    let mut expected: Vec<Address> = (1..=3).chain(194..=200).map(address).collect();
+    expected.sort();
+    assert_eq!(addresses, expected);
+    assert_eq!(registry.metrics().evictions, 190);
+
+    // Re-registering an evicted address evicts the least recently used one
+    registry.register(provider.clone(), address(100));
+    assert!(registry.contains(address(100)));
+    assert!(!registry.contains(address(194)));
+    assert_eq!(registry.len(), 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ttl_expiration() -> Result<(), Box<dyn std::error::Error>> {
+    let config = RegistryConfig {
+        ttl: Some(Duration::from_millis(50)),
+        ..RegistryConfig::default()
+    };
+    let registry = Erc20TokenRegistry::<MockProvider>::mock().with_config(config);
+    let provider = registry.provider().await?;
+
+    registry.register(provider.clone(), address(1));
+    assert!(registry.get(address(1)).is_some());
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    registry.register(provider.clone(), address(2));
+
+    assert!(registry.get(address(1)).is_none());
+    assert_eq!(registry.addresses(), vec![address(2)]);
+    assert_eq!(registry.metrics().expirations, 1);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(registry.is_empty());
+    assert_eq!(registry.purge_expired(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unbounded_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+
+    for n in 1..=100 {
+        registry.register(provider.clone(), address(n));
+    }
+    assert_eq!(registry.len(), 100);
+    assert_eq!(registry.config(), RegistryConfig::default());
+    assert_eq!(registry.metrics().evictions, 0);
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use ethers::{
+    abi::{encode, AbiEncode, Token},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes},
+    utils::rlp::Rlp,
+};
+use ethers_abirpc::prelude::*;
+use serde_json::{json, Value};
+use std::{path::Path, process::Output};
+use tokio::process::Command;
+
+use common::{block_range, error, method, requested, result, serve};
+
+mod common;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_OWNER: &str = "0x000000000000000000000000000000000000dEaD";
+
+async fn abirpc(url: &str, args: &[&str]) -> Result<Output, Box<dyn std::error::Error>> {
+    let output = Command::new(env!("CARGO_BIN_EXE_abirpc"))
+        .args(["--rpc-url", url, "--chain", "1"])
+        .args(args)
+        .env_remove("ABIRPC_RPC_URL")
+        .env_remove("ABIRPC_CHAIN")
+        .env_remove("ABIRPC_PASSWORD_FILE")
+        .output()
+        .await?;
+
+    Ok(output)
+}
+
+#[tokio::test]
+async fn test_cli_arguments() -> Result<(), Box<dyn std::error::Error>> {
+    let url = "http://127.0.0.1:1";
+
+    // Missing required options are rejected before connecting
+    let output = abirpc(url, &["call", "--address", TEST_ADDRESS, "decimals"]).await?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)?.contains("--abi <ABI>"));
+
+    let output = abirpc(
+        url,
+        &["call", "--abi", "x.json", "--address", "0x12", "decimals"],
+    )
+    .await?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)?.contains("--address"));
+
+    let output = abirpc(url, &["logs", "--abi", "x.json", "--address", TEST_ADDRESS]).await?;
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)?.contains("<EVENT>"));
+
+    let output = abirpc(url, &["--chain", "notachain", "doctor"]).await?;
+    assert_eq!(output.status.code(), Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_call() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(|request| match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_call" => result(json!(Bytes::from(encode(&[Token::Uint(U256::exp10(18))])))),
+        method => error(-32000, &format!("unexpected {method}")),
+    })
+    .await?;
+
+    let args = [
+        "call",
+        "--abi",
+        "./tests/abi/Erc20Token.json",
+        "--address",
+        TEST_ADDRESS,
+        "balanceOf",
+        TEST_OWNER,
+    ];
+    let output = abirpc(&url, &args).await?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8(output.stderr)?
+    );
+    let value: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(value, json!("1000000000000000000"));
+
+    let calls = requested(&requests, "eth_call");
+    assert_eq!(calls.len(), 1);
+    let expected = BalanceOfCall {
+        account: address_from!(TEST_OWNER)?,
+    };
+    let to: Address = serde_json::from_value(calls[0][0]["to"].clone())?;
+    let data: Bytes = serde_json::from_value(calls[0][0]["data"].clone())?;
+    assert_eq!(to, address_from!(TEST_ADDRESS)?);
+    assert_eq!(data, Bytes::from(expected.encode()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_send() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(|request| match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_gasPrice" => result(json!("0x3b9aca00")),
+        "eth_estimateGas" => result(json!("0xc350")),
+        "eth_getTransactionCount" => result(json!("0x7")),
+        "eth_sendRawTransaction" => error(-32000, "insufficient funds"),
+        method => error(-32000, &format!("unexpected {method}")),
+    })
+    .await?;
+
+    let dir = std::env::temp_dir().join(format!("abirpc-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let (wallet, _) = LocalWallet::new_keystore(
+        &dir,
+        &mut ethers::core::rand::thread_rng(),
+        "password",
+        Some("keystore.json"),
+    )?;
+    let password = dir.join("password");
+    std::fs::write(&password, "password\n")?;
+    let keystore = dir.join("keystore.json");
+
+    let args = [
+        "send",
+        "--abi",
+        "./tests/abi/Erc20Token.json",
+        "--address",
+        TEST_ADDRESS,
+        "--keystore",
+        path(&keystore)?,
+        "--password-file",
+        path(&password)?,
+        "--value",
+        "5",
+        "transfer",
+        TEST_OWNER,
+        "1.5 ether",
+    ];
+    let output = abirpc(&url, &args).await?;
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)?.contains("insufficient funds"));
+
+    // The signed transaction carries the encoded call
+    let sent = requested(&requests, "eth_sendRawTransaction");
+    assert_eq!(sent.len(), 1);
+    let raw: Bytes = serde_json::from_value(sent[0][0].clone())?;
+    let (tx, signature) = TypedTransaction::decode_signed(&Rlp::new(&raw))?;
+    let expected = TransferCall {
+        to: address_from!(TEST_OWNER)?,
+        value: U256::exp10(18) * 3 / 2,
+    };
+    assert_eq!(tx.to_addr(), Some(&address_from!(TEST_ADDRESS)?));
+    assert_eq!(tx.data(), Some(&Bytes::from(expected.encode())));
+    assert_eq!(tx.value(), Some(&U256::from(5)));
+    assert_eq!(tx.nonce(), Some(&U256::from(7)));
+    assert_eq!(tx.chain_id(), Some(1u64.into()));
+    assert_eq!(signature.recover(tx.sighash())?, wallet.address());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_logs_window() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(|request| match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_blockNumber" => result(json!("0x1312d00")), // 20_000_000
+        "eth_getLogs" => result(json!([])),
+        method => error(-32000, &format!("unexpected {method}")),
+    })
+    .await?;
+
+    let args = [
+        "logs",
+        "--abi",
+        "./tests/abi/Erc20Token.json",
+        "--address",
+        TEST_ADDRESS,
+        "Transfer",
+    ];
+    let output = abirpc(&url, &args).await?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8(output.stderr)?
+    );
+
+    // Without --from-block only the recent blocks are searched
+    let filter = &requested(&requests, "eth_getLogs")[0][0];
+    assert_eq!(filter["fromBlock"], json!("0x1312919")); // 19_999_001
+    assert_eq!(filter["toBlock"], json!("0x1312d00"));
+
+    let args = [&args[..], &["--from-block", "100", "--to-block", "200"]].concat();
+    let output = abirpc(&url, &args).await?;
+    assert!(output.status.success());
+    let filter = &requested(&requests, "eth_getLogs")[1][0];
+    assert_eq!(filter["fromBlock"], json!("0x64"));
+    assert_eq!(filter["toBlock"], json!("0xc8"));
+    assert_eq!(requested(&requests, "eth_blockNumber").len(), 1);
+
+    Ok(())
+}
+
+fn path(path: &Path) -> Result<&str, Box<dyn std::error::Error>> {
+    Ok(path.to_str().ok_or("non-utf8 path")?)
+}
+
+/// Node without the debug namespace which limits `eth_getLogs` to 2000 blocks.
+fn node(chain_id: &'static str) -> impl Fn(&Value) -> Value {
+    move |request| match method(request) {
+        "eth_chainId" => result(json!(chain_id)),
+        "eth_blockNumber" => result(json!("0x4e20")),
+        "eth_getBlockByNumber" => {
+            let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+            result(json!({
+                "hash": format!("0x{}", "11".repeat(32)),
+                "parentHash": format!("0x{}", "00".repeat(32)),
+                "number": "0x4e20",
+                "timestamp": format!("{:#x}", now.map_or(0, |now| now.as_secs())),
+                "transactions": [],
+                "uncles": [],
+            }))
+        }
+        "eth_getBalance" => result(json!("0x0")),
+        "eth_feeHistory" => {
+            result(json!({ "oldestBlock": "0x4e20", "baseFeePerGas": [], "gasUsedRatio": [] }))
+        }
+        "eth_getLogs" if block_range(request) > 2_000 => error(-32000, "block range too large"),
+        "eth_getLogs" => result(json!([])),
+        "trace_transaction" => result(Value::Null),
+        _ => error(-32000, "the method does not exist"),
+    }
+}
+
+fn check<'a>(report: &'a Value, name: &str) -> &'a Value {
+    let checks = report["checks"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    checks
+        .iter()
+        .find(|check| check["name"] == name)
+        .unwrap_or(&Value::Null)
+}
+
+#[tokio::test]
+async fn test_cli_doctor() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(node("0x1")).await?;
+
+    let output = abirpc(&url, &["doctor", "--json"]).await?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8(output.stderr)?
+    );
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["chain_id"], json!(1));
+    for name in [
+        "chain id",
+        "head block",
+        "archive",
+        "fee history",
+        "trace",
+        "latency",
+    ] {
+        assert_eq!(check(&report, name)["status"], json!("ok"), "{name}");
+    }
+    assert_eq!(check(&report, "debug")["status"], json!("warn"));
+    assert_eq!(check(&report, "debug")["detail"], json!("not supported"));
+    assert_eq!(check(&report, "logs range")["detail"], json!("2000 blocks"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cli_doctor_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(node("0x2")).await?;
+
+    // The report is printed before failing checks set the exit code
+    let output = abirpc(&url, &["doctor"]).await?;
+    assert_eq!(output.status.code(), Some(1));
+    let report = String::from_utf8(output.stdout)?;
+    assert!(report.contains("[FAIL] chain id"));
+    assert!(report.contains("[warn] debug"));
+    assert!(String::from_utf8(output.stderr)?.contains("ChecksFailed"));
+
+    // Unreachable endpoints fail every check
+    let output = abirpc("http://127.0.0.1:1", &["doctor", "--json"]).await?;
+    assert_eq!(output.status.code(), Some(1));
+    let report: Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(check(&report, "chain id")["status"], json!("fail"));
+
+    Ok(())
+}
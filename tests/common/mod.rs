@@ -0,0 +1,134 @@
+#![allow(dead_code)]
+
+use {
+    serde_json::{json, Value},
+    std::sync::{Arc, Mutex},
+    tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    },
+};
+
+/// Request bodies received by `serve`, in order. Batches are kept as arrays.
+pub type Requests = Arc<Mutex<Vec<Value>>>;
+
+/// HTTP JSON-RPC endpoint answering every request with `respond`, which
+/// returns the `result` or `error` member of the response. Batch arrays are
+/// answered request by request.
+pub async fn serve<F>(respond: F) -> Result<(String, Requests), Box<dyn std::error::Error>>
+where
+    F: Fn(&Value) -> Value + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("http://{}", listener.local_addr()?);
+    let requests = Requests::default();
+    let respond = Arc::new(respond);
+
+    let recorded = Arc::clone(&requests);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let (respond, recorded) = (Arc::clone(&respond), Arc::clone(&recorded));
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                while let Some(body) = read_request(&mut stream).await {
+                    let Ok(body) = serde_json::from_slice::<Value>(&body) else {
+                        return;
+                    };
+                    if let Ok(mut recorded) = recorded.lock() {
+                        recorded.push(body.clone());
+                    }
+
+                    let answer = |request: &Value| {
+                        let mut response = respond(request);
+                        response["jsonrpc"] = json!("2.0");
+                        response["id"] = request["id"].clone();
+                        response
+                    };
+                    let response = match &body {
+                        Value::Array(requests) => requests.iter().map(answer).collect(),
+                        request => answer(request),
+                    }
+                    .to_string();
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{response}",
+                        response.len()
+                    );
+                    if stream
+                        .get_mut()
+                        .write_all(response.as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    Ok((url, requests))
+}
+
+/// Body of the next request on `stream`, `None` once the connection closes.
+async fn read_request<S>(stream: &mut BufReader<S>) -> Option<Vec<u8>>
+where
+    S: tokio::io::AsyncRead + Unpin,
+{
+    let mut content_length = 0;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if stream.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+        if line == "\r\n" {
+            break;
+        }
+        let header = line.to_ascii_lowercase();
+        if let Some(length) = header.strip_prefix("content-length:") {
+            content_length = length.trim().parse().ok()?;
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    stream.read_exact(&mut body).await.ok()?;
+
+    Some(body)
+}
+
+pub fn result(result: Value) -> Value {
+    json!({ "result": result })
+}
+
+pub fn error(code: i64, message: &str) -> Value {
+    json!({ "error": { "code": code, "message": message } })
+}
+
+pub fn method(request: &Value) -> &str {
+    request["method"].as_str().unwrap_or_default()
+}
+
+/// Params of every recorded `method` request, batched or not.
+pub fn requested(requests: &Requests, method: &str) -> Vec<Value> {
+    let requests = requests.lock().map(|r| r.clone()).unwrap_or_default();
+    requests
+        .iter()
+        .flat_map(|body| match body {
+            Value::Array(batch) => batch.clone(),
+            request => vec![request.clone()],
+        })
+        .filter(|request| request["method"] == method)
+        .map(|request| request["params"].clone())
+        .collect()
+}
+
+/// Number of blocks covered by the filter of an `eth_getLogs` request.
+pub fn block_range(request: &Value) -> u64 {
+    let block = |key: &str| {
+        let block = request["params"][0][key].as_str().unwrap_or_default();
+        u64::from_str_radix(block.trim_start_matches("0x"), 16).unwrap_or_default()
+    };
+
+    block("toBlock") - block("fromBlock") + 1
+}
@@ -0,0 +1,50 @@
+use ethers::types::Address;
+use ethers_abirpc::{prelude::*, registry::AbiRegistry};
+use std::{panic, sync::Arc};
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_register() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Arc::new(Erc20TokenRegistry::<MockProvider>::mock());
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+
+    let handles: Vec<_> = (0..32)
+        .map(|_| {
+            let registry = Arc::clone(&registry);
+            let provider = provider.clone();
+            tokio::spawn(async move { registry.register(provider, address).address() })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.await?, address);
+    }
+
+    assert_eq!(registry.len(), 1);
+    let metrics = registry.metrics();
+    assert_eq!((metrics.hits, metrics.misses), (31, 1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_panic_does_not_poison() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = AbiRegistry::<u64>::mock();
+    let address = Address::from_low_u64_be(1);
+    registry.add_entry(address, 1);
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        registry.update_metadata(address, |_| panic!("panic while holding a registry guard"));
+    }));
+    assert!(result.is_err());
+
+    registry.add_entry(Address::from_low_u64_be(2), 2);
+    assert_eq!(registry.get_entry(address), Some(1));
+    assert_eq!(registry.len(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const TEST_OP_USDC: &str = "0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85";
+
+#[tokio::test]
+async fn test_chain_serde() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(serde_json::to_string(&Chain::Id(1))?, "1");
+    assert_eq!(serde_json::from_str::<Chain>("10")?, Chain::Id(10));
+    assert_eq!(
+        serde_json::from_str::<Chain>("\"optimism\"")?,
+        Chain::Id(10)
+    );
+    assert!(serde_json::from_str::<Chain>("\"not-a-chain\"").is_err());
+
+    let chain = Chain::ChainConfig(ChainConfig {
+        chain_id: 10,
+        retry_client_config: RetryClientConfig {
+            rate_limit_retries: 20,
+            ..RetryClientConfig::default()
+        },
+        assert_chain_id: true,
+        ..ChainConfig::default()
+    });
+    let toml = toml::to_string(&chain)?;
+    assert_eq!(toml::from_str::<Chain>(&toml)?, chain);
+
+    let config: ChainConfig = toml::from_str("chain_id = \"base\"\nprobe_capabilities = true")?;
+    assert_eq!(config.chain_id, 8453);
+    assert!(config.probe_capabilities);
+    assert_eq!(config.retry_client_config, RetryClientConfig::default());
+    assert!(toml::from_str::<ChainConfig>("probe_capabilities = true").is_err());
+
+    assert_eq!(serde_json::to_string(&NamedChain::Mainnet)?, "\"Mainnet\"");
+    assert_eq!(
+        serde_json::from_str::<NamedChain>("\"arbitrum\"")?,
+        NamedChain::Arbitrum
+    );
+    assert_eq!(
+        serde_json::from_str::<NamedChain>("10")?,
+        NamedChain::Optimism
+    );
+    assert!(serde_json::from_str::<NamedChain>("123456789").is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_formats() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("ABIRPC_TEST_API_KEY", "secret");
+    let toml = Config::from_file("./tests/config/chains.toml")?;
+    let yaml = Config::from_file("./tests/config/chains.yaml")?;
+    assert_eq!(toml, yaml);
+
+    let mainnet = toml.endpoint(Chain::from(NamedChain::Mainnet))?;
+    assert_eq!(mainnet.url, "http://127.0.0.1:8545/v2/secret");
+    assert_eq!(mainnet.chain, Chain::Id(1));
+    assert_eq!(mainnet.addresses["WETH"], address_from!(TEST_WETH)?);
+
+    let optimism = toml.endpoint(Chain::Id(10))?;
+    let Chain::ChainConfig(config) = optimism.chain else {
+        return Err("expected a chain config".into());
+    };
+    assert_eq!(config.chain_id, 10);
+    assert!(!config.assert_chain_id);
+    assert!(!config.probe_capabilities);
+    assert_eq!(config.retry_client_config.rate_limit_retries, 20);
+    assert_eq!(
+        config.retry_client_config.initial_backoff_ms,
+        Chain::Id(10).retry_client_config().initial_backoff_ms
+    );
+
+    assert_eq!(toml.chains(), vec![mainnet.chain, optimism.chain]);
+    assert_eq!(
+        toml.address_book().get(Chain::Id(10), "USDC"),
+        Some(address_from!(TEST_OP_USDC)?)
+    );
+    assert!(matches!(
+        toml.endpoint(Chain::Id(137)),
+        Err(Error::UnknownChain { chain_id: 137 })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let err =
+        Config::from_toml("[chains.mainnet]\nurl = \"http://${ABIRPC_TEST_UNSET}\"").unwrap_err();
+    assert!(matches!(err, Error::ConfigError(_)));
+
+    let err =
+        Config::from_toml("[chains.mainnet]\nurl = \"http://${ABIRPC_TEST_UNSET\"").unwrap_err();
+    assert!(matches!(err, Error::ConfigError(_)));
+
+    let err =
+        Config::from_toml("[chains.mainnet]\nurl = \"\"\n[chains.1]\nurl = \"\"").unwrap_err();
+    assert!(matches!(err, Error::ConfigError(_)));
+
+    let err = Config::from_toml("[chains.mainnet]\nurl = \"\"\nretries = 3").unwrap_err();
+    assert!(matches!(err, Error::TomlError(_)));
+
+    let err = Config::from_toml("[chain.mainnet]\nurl = \"\"").unwrap_err();
+    assert!(matches!(err, Error::TomlError(_)));
+
+    let err = Config::from_file("./tests/config/missing.toml").unwrap_err();
+    assert!(matches!(err, Error::IoError(_)));
+
+    let path = std::env::temp_dir().join(format!("abirpc-config-{}.ini", std::process::id()));
+    std::fs::write(&path, "[chains.mainnet]\nurl = \"http://127.0.0.1:8545\"")?;
+    let err = Config::from_file(&path).unwrap_err();
+    std::fs::remove_file(&path)?;
+    assert!(matches!(err, Error::UnsupportedFormat(ref file) if file.ends_with(".ini")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_from_config() -> Result<(), Box<dyn std::error::Error>> {
+    // ABIRPC_TEST_API_KEY is only set by test_config_formats
+    let yaml = std::fs::read_to_string("./tests/config/chains.yaml")?;
+    let config = Config::from_yaml(&yaml.replace("${ABIRPC_TEST_API_KEY}", "registry"))?;
+
+    let registry = Erc20TokenRegistry::<HttpProvider>::from_config(
+        &config,
+        NamedChain::Optimism.into(),
+        &["USDC"],
+    )
+    .await?;
+    assert_eq!(registry.resolve("USDC"), Some(address_from!(TEST_OP_USDC)?));
+    assert_eq!(registry.len(), 1);
+
+    let err = Erc20TokenRegistry::<HttpProvider>::from_config(
+        &config,
+        NamedChain::Optimism.into(),
+        &["WETH"],
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, Error::UnknownAlias { chain_id: 10, .. }));
+    assert_eq!(
+        registry.chain(),
+        Some(config.endpoint(Chain::Id(10))?.chain)
+    );
+
+    let config = Config::from_toml(
+        "[chains.mainnet]
+url = \"http://127.0.0.1:8545\"
+assert_chain_id = false
+addresses = { WETH = \"0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2\", POOL = \"0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc\" }
+
+[chains.optimism]
+url = \"http://127.0.0.1:9545\"
+assert_chain_id = false
+addresses = { USDC = \"0x0b2C639c533813f4Aa9D7837CAf62653d097Ff85\" }",
+    )?;
+    let registry =
+        Erc20TokenMultiChainRegistry::<HttpProvider>::from_config(&config, &["WETH", "USDC"])
+            .await?;
+    assert_eq!(registry.chains().len(), 2);
+    assert_eq!(registry.entries().len(), 2);
+    assert!(registry
+        .get(Chain::Id(1), address_from!(TEST_WETH)?)
+        .is_some());
+    assert!(registry
+        .get(Chain::Id(10), address_from!(TEST_OP_USDC)?)
+        .is_some());
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use ethers::{
+    abi::{encode, Token},
+    providers::Provider,
+    types::Bytes,
+};
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+abigen!(Erc721Token, "./tests/abi/Erc721Token.json");
+abirpc!(Erc721Token);
+
+abigen!(Vault, "./tests/abi/Vault.json");
+abirpc!(Vault);
+
+const TEST_TOKEN: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_NFT: &str = "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D"; // BAYC
+const TEST_VAULT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+
+fn mock_provider() -> MockProvider {
+    Provider::new(ethers::providers::MockProvider::new())
+}
+
+#[tokio::test]
+async fn test_typed_retrieval() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = ContractRegistry::new(mock_provider(), Chain::Id(1));
+    let (token, nft, vault) = (
+        address_from!(TEST_TOKEN)?,
+        address_from!(TEST_NFT)?,
+        address_from!(TEST_VAULT)?,
+    );
+
+    registry.register::<Erc20Token<MockProvider>>(token);
+    registry.register::<Erc721Token<MockProvider>>(nft);
+    registry.register::<Vault<MockProvider>>(vault);
+    assert_eq!(registry.len(), 3);
+
+    let instance = registry
+        .get::<Erc20Token<MockProvider>>(token)
+        .ok_or("missing token")?;
+    assert_eq!(instance.address(), token);
+    assert!(registry.get::<Erc721Token<MockProvider>>(token).is_none());
+    assert!(registry.contains::<Vault<MockProvider>>(vault));
+    assert_eq!(registry.entries::<Erc721Token<MockProvider>>().len(), 1);
+
+    // Instances share the registry provider
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(18))]));
+    registry
+        .provider()
+        .as_ref()
+        .as_ref()
+        .push::<Bytes, _>(decimals)?;
+    assert_eq!(instance.decimals().call().await?, 18);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multiple_abis_per_address() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = ContractRegistry::mock(mock_provider());
+    let address = address_from!(TEST_VAULT)?;
+
+    registry.register::<Vault<MockProvider>>(address);
+    registry.register::<Erc20Token<MockProvider>>(address);
+    registry.register::<Erc20Token<MockProvider>>(address);
+
+    assert_eq!(registry.len(), 2);
+    assert_eq!(registry.addresses(), vec![address]);
+    assert_eq!(registry.names(address), vec!["Erc20Token", "Vault"]);
+
+    let removed = registry.unregister::<Vault<MockProvider>>(address);
+    assert_eq!(removed.map(|vault| vault.contract_address()), Some(address));
+    assert_eq!(registry.names(address), vec!["Erc20Token"]);
+
+    registry.clear();
+    assert!(registry.is_empty());
+
+    Ok(())
+}
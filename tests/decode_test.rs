@@ -0,0 +1,159 @@
+use ethers::{
+    abi::{encode, AbiEncode, Token},
+    types::{Address, Bytes, Log, Transaction, TransactionReceipt, H256},
+};
+use ethers_abirpc::prelude::*;
+use serde_json::json;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_OWNER: &str = "0x000000000000000000000000000000000000dEaD";
+
+fn transfer_log(address: Address, to: Address, value: u64) -> Log {
+    Log {
+        address,
+        topics: vec![
+            TransferFilter::signature(),
+            H256::from(Address::zero()),
+            H256::from(to),
+        ],
+        data: Bytes::from(encode(&[Token::Uint(U256::from(value))])),
+        ..Log::default()
+    }
+}
+
+#[tokio::test]
+async fn test_decode_calldata() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let owner = address_from!(TEST_OWNER)?;
+
+    // Entries registered after add_to_decoder are resolved
+    let decoder = TxDecoder::new();
+    registry.add_to_decoder(&decoder);
+    assert!(decoder.is_empty());
+    registry.register(provider, address);
+    assert_eq!(decoder.contract(address).as_deref(), Some("Erc20Token"));
+    assert_eq!(decoder.len(), 1);
+
+    let data = TransferCall {
+        to: owner,
+        value: U256::from(42),
+    }
+    .encode();
+    let call = decoder
+        .decode_calldata(address, &data)?
+        .ok_or("missing call")?;
+    assert_eq!(call.function, "transfer");
+    assert_eq!(call.signature, "transfer(address,uint256)");
+    assert_eq!(call.args["to"], json!(TEST_OWNER));
+    assert_eq!(call.args["value"], json!("42"));
+
+    assert!(decoder.decode_calldata(owner, &data)?.is_none());
+    let err = decoder.decode_calldata(address, &[0xde, 0xad, 0xbe, 0xef]);
+    assert!(matches!(err, Err(Error::UnknownFunction(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decode_transaction() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let owner = address_from!(TEST_OWNER)?;
+    registry.register(provider.clone(), address);
+
+    let decoder = TxDecoder::new();
+    registry.add_to_decoder(&decoder);
+
+    let hash = H256::repeat_byte(0x11);
+    let tx = Transaction {
+        hash,
+        from: owner,
+        to: Some(address),
+        input: TransferCall {
+            to: owner,
+            value: U256::from(42),
+        }
+        .encode()
+        .into(),
+        ..Transaction::default()
+    };
+    let unknown = Log {
+        address,
+        topics: vec![H256::repeat_byte(0x22)],
+        ..Log::default()
+    };
+    let receipt = TransactionReceipt {
+        transaction_hash: hash,
+        logs: vec![
+            transfer_log(address, owner, 42),
+            transfer_log(owner, owner, 7), // Unregistered address
+            unknown.clone(),
+        ],
+        ..TransactionReceipt::default()
+    };
+
+    // MockProvider responses are returned in reverse order
+    provider.as_ref().push(receipt.clone())?;
+    provider.as_ref().push(tx.clone())?;
+
+    let decoded = decoder.decode_transaction(&provider, hash).await?;
+    assert_eq!(decoded.undecoded_call, None);
+    let call = decoded.call.ok_or("missing call")?;
+    assert_eq!(call.contract, "Erc20Token");
+    assert_eq!(call.function, "transfer");
+    assert_eq!(decoded.logs.len(), 1);
+    assert_eq!(decoded.logs[0].event.name, "Transfer");
+    assert_eq!(decoded.logs[0].event.params["value"], json!("42"));
+    assert_eq!(decoded.undecoded, vec![unknown]);
+
+    provider.as_ref().push(Option::<Transaction>::None)?;
+    let err = decoder.decode_transaction(&provider, hash).await;
+    assert!(matches!(err, Err(Error::TransactionNotFound(_))));
+
+    // Selectors missing from the ABI are kept raw and logs are still decoded
+    let input = Bytes::from(vec![0xde, 0xad, 0xbe, 0xef, 0x01]);
+    let unknown_call = Transaction {
+        input: input.clone(),
+        ..tx.clone()
+    };
+    provider.as_ref().push(receipt)?;
+    provider.as_ref().push(unknown_call)?;
+    let decoded = decoder.decode_transaction(&provider, hash).await?;
+    assert!(decoded.call.is_none());
+    assert_eq!(decoded.undecoded_call, Some(input));
+    assert_eq!(decoded.logs.len(), 1);
+
+    // Arguments which do not decode against a known selector are an error
+    let mut truncated = tx.input.to_vec();
+    truncated.truncate(20);
+    let malformed = Transaction {
+        input: Bytes::from(truncated),
+        ..tx.clone()
+    };
+    provider.as_ref().push(TransactionReceipt::default())?;
+    provider.as_ref().push(malformed)?;
+    let err = decoder.decode_transaction(&provider, hash).await;
+    assert!(matches!(err, Err(Error::AbiError(_))));
+
+    // Plain transfers have no call to decode
+    let transfer = Transaction {
+        input: Bytes::default(),
+        ..tx
+    };
+    provider.as_ref().push(TransactionReceipt::default())?;
+    provider.as_ref().push(transfer)?;
+    let decoded = decoder.decode_transaction(&provider, hash).await?;
+    assert!(decoded.call.is_none());
+
+    // Unregistered entries are no longer decoded
+    registry.unregister(address);
+    assert!(!decoder.contains(address));
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use ethers::{
+    abi::{encode, Abi, Token},
+    types::{Address, Block, Bytes, FeeHistory, Transaction, TransactionReceipt, H256, U64},
+    utils::get_create2_address,
+};
+use ethers_abirpc::{deploy::init_code, prelude::*};
+use std::time::Duration;
+
+abigen!(Vault, "./tests/abi/Vault.json");
+abirpc!(Vault);
+
+const TEST_OWNER: &str = "0x000000000000000000000000000000000000dEaD";
+const TEST_DEPLOYED: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+const TEST_BLOCK: u64 = 19_000_000;
+
+fn bytecode() -> Bytes {
+    Bytes::from(vec![0x60, 0x80, 0x60, 0x40, 0x52, 0x34, 0x80, 0x15])
+}
+
+fn receipt(contract_address: Option<Address>, status: u64) -> TransactionReceipt {
+    TransactionReceipt {
+        transaction_hash: H256::repeat_byte(0x11),
+        block_number: Some(U64::from(TEST_BLOCK)),
+        contract_address,
+        status: Some(U64::from(status)),
+        ..TransactionReceipt::default()
+    }
+}
+
+// Responses for an EIP-1559 send followed by PendingTransaction polling. MockProvider
+// responses are returned in reverse order.
+fn push_transaction(
+    provider: &MockProvider,
+    receipt: TransactionReceipt,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let tx = Transaction {
+        hash: receipt.transaction_hash,
+        block_number: receipt.block_number,
+        ..Transaction::default()
+    };
+    let fee_history = FeeHistory {
+        base_fee_per_gas: vec![U256::from(1_000_000_000)],
+        gas_used_ratio: vec![0.5],
+        oldest_block: U256::from(TEST_BLOCK),
+        reward: vec![vec![U256::from(1_000_000)]],
+    };
+    let block = Block::<H256> {
+        base_fee_per_gas: Some(U256::from(1_000_000_000)),
+        ..Block::default()
+    };
+
+    provider
+        .as_ref()
+        .push::<TransactionReceipt, _>(receipt.clone())?;
+    provider.as_ref().push::<Transaction, _>(tx)?;
+    provider
+        .as_ref()
+        .push::<H256, _>(receipt.transaction_hash)?;
+    provider.as_ref().push::<U256, _>(U256::from(500_000))?;
+    provider.as_ref().push::<FeeHistory, _>(fee_history)?;
+    provider.as_ref().push::<Block<H256>, _>(block)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deploy() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let provider = registry
+        .provider()
+        .await?
+        .interval(Duration::from_millis(1));
+    let deployed = address_from!(TEST_DEPLOYED)?;
+
+    push_transaction(&provider, receipt(Some(deployed), 1))?;
+    let instance = registry
+        .deploy(provider.clone(), bytecode(), address_from!(TEST_OWNER)?)
+        .await?;
+
+    assert_eq!(instance.address(), deployed);
+    assert!(registry.contains(deployed));
+    assert_eq!(
+        registry.metadata(deployed).and_then(|m| m.deployment_block),
+        Some(TEST_BLOCK)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deploy_reverted() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let provider = registry
+        .provider()
+        .await?
+        .interval(Duration::from_millis(1));
+    let deployed = address_from!(TEST_DEPLOYED)?;
+
+    push_transaction(&provider, receipt(Some(deployed), 0))?;
+    let err = registry
+        .deploy(provider.clone(), bytecode(), address_from!(TEST_OWNER)?)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::DeploymentFailed { .. }));
+    assert!(registry.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_init_code_without_constructor() -> Result<(), Box<dyn std::error::Error>> {
+    let abi = Abi::default();
+    let owner = address_from!(TEST_OWNER)?;
+
+    assert!(matches!(
+        init_code(&abi, bytecode(), owner),
+        Err(Error::MissingConstructor)
+    ));
+    assert_eq!(init_code(&abi, bytecode(), ())?, bytecode());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create2_address() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let owner = address_from!(TEST_OWNER)?;
+    let salt = H256::repeat_byte(0x01);
+
+    let mut init_code = bytecode().to_vec();
+    init_code.extend(encode(&[Token::Address(owner)]));
+    let expected = get_create2_address(CREATE2_DEPLOYER, salt, init_code);
+
+    assert_eq!(registry.create2_address(bytecode(), owner, salt)?, expected);
+    assert_ne!(
+        registry.create2_address(bytecode(), owner, H256::zero())?,
+        expected
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deploy_create2() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let provider = registry
+        .provider()
+        .await?
+        .interval(Duration::from_millis(1));
+    let owner = address_from!(TEST_OWNER)?;
+    let salt = H256::repeat_byte(0x01);
+    let address = registry.create2_address(bytecode(), owner, salt)?;
+
+    push_transaction(&provider, receipt(None, 1))?;
+    provider.as_ref().push::<Bytes, _>(Bytes::new())?;
+    let instance = registry
+        .deploy_create2(provider.clone(), bytecode(), owner, salt)
+        .await?;
+
+    assert_eq!(instance.address(), address);
+    assert_eq!(
+        registry.metadata(address).and_then(|m| m.deployment_block),
+        Some(TEST_BLOCK)
+    );
+
+    // Deployed contracts are registered without sending a transaction
+    registry.clear();
+    provider.as_ref().push::<Bytes, _>(bytecode())?;
+    let instance = registry
+        .deploy_create2(provider.clone(), bytecode(), owner, salt)
+        .await?;
+    assert_eq!(instance.address(), address);
+    assert!(registry.metadata(address).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deploy_create2_reverted() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let provider = registry
+        .provider()
+        .await?
+        .interval(Duration::from_millis(1));
+    let salt = H256::repeat_byte(0x02);
+
+    push_transaction(&provider, receipt(None, 0))?;
+    provider.as_ref().push::<Bytes, _>(Bytes::new())?;
+    let err = registry
+        .deploy_create2(
+            provider.clone(),
+            bytecode(),
+            address_from!(TEST_OWNER)?,
+            salt,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::DeploymentFailed { .. }));
+    assert!(registry.is_empty());
+
+    Ok(())
+}
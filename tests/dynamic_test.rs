@@ -0,0 +1,207 @@
+use ethers::{
+    abi::{encode, Token},
+    types::{Address, Bytes, Filter, Log, H256, I256, U256},
+};
+use ethers_abirpc::prelude::*;
+use serde_json::json;
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_OWNER: &str = "0x000000000000000000000000000000000000dEaD";
+
+fn erc20_abi() -> Result<String, Box<dyn std::error::Error>> {
+    Ok(std::fs::read_to_string("./tests/abi/Erc20Token.json")?)
+}
+
+#[tokio::test]
+async fn test_dynamic_call() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = DynamicRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let instance = registry.register_json(provider.clone(), address, &erc20_abi()?)?;
+    assert_eq!(registry.get(address).map(|c| c.address()), Some(address));
+
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(6))]));
+    provider.as_ref().push::<Bytes, _>(decimals)?;
+    assert_eq!(instance.call("decimals", &[]).await?, json!("6"));
+
+    let balance = Bytes::from(encode(&[Token::Uint(U256::exp10(24))]));
+    provider.as_ref().push::<Bytes, _>(balance)?;
+    let value = instance.call("balanceOf", &[json!(TEST_OWNER)]).await?;
+    assert_eq!(value, json!("1000000000000000000000000"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dynamic_encode() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let provider = std::sync::Arc::new(provider);
+    let instance =
+        DynamicContract::from_json(address_from!(TEST_ADDRESS)?, &erc20_abi()?, provider)?;
+    let owner = address_from!(TEST_OWNER)?;
+
+    let expected = instance.encode_tokens(
+        "transfer",
+        &[Token::Address(owner), Token::Uint(U256::exp10(18) * 3 / 2)],
+    )?;
+    for amount in [
+        json!("1500000000000000000"),
+        json!("0x14d1120d7b160000"),
+        json!("1.5 ether"),
+    ] {
+        let data = instance.encode("transfer(address,uint256)", &[json!(TEST_OWNER), amount])?;
+        assert_eq!(data, expected);
+    }
+
+    let err = instance.encode("transfer", &[json!(TEST_OWNER), json!(true)]);
+    assert!(matches!(err, Err(Error::InvalidArgument(_))));
+    let err = instance.encode("transfer", &[json!(TEST_OWNER)]);
+    assert!(matches!(err, Err(Error::InvalidArgument(_))));
+    let err = instance.encode("mintTo", &[]);
+    assert!(matches!(err, Err(Error::UnknownFunction(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dynamic_overloads() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let abi = std::fs::read_to_string("./tests/abi/Erc721Token.json")?;
+    let instance = DynamicContract::from_json(
+        address_from!(TEST_ADDRESS)?,
+        &abi,
+        std::sync::Arc::new(provider),
+    )?;
+
+    let function = instance.function("safeTransferFrom", Some(4))?;
+    assert_eq!(function.inputs.len(), 4);
+    let function = instance.function("safeTransferFrom(address,address,uint256)", None)?;
+    assert_eq!(function.inputs.len(), 3);
+
+    match instance.function("safeTransferFrom", None) {
+        Err(Error::AmbiguousOverload { name, candidates }) => {
+            assert_eq!(name, "safeTransferFrom");
+            assert_eq!(candidates.len(), 2);
+            assert!(candidates.contains(&String::from("safeTransferFrom(address,address,uint256)")));
+        }
+        result => panic!("expected an ambiguous overload, got {result:?}"),
+    }
+    let err = instance.encode("safeTransferFrom", &[json!(TEST_OWNER)]);
+    assert!(matches!(
+        err,
+        Err(Error::NoMatchingOverload { arg_count: 1, ref candidates, .. }) if candidates.len() == 2
+    ));
+
+    let event = instance.event("Transfer(address,address,uint256)")?;
+    assert_eq!(event.name, "Transfer");
+    let err = instance.event("Transfer(address,uint256)");
+    assert!(matches!(err, Err(Error::UnknownEvent(_))));
+
+    let abi = json!([
+        { "type": "event", "name": "Log", "inputs": [], "anonymous": false },
+        {
+            "type": "event",
+            "name": "Log",
+            "inputs": [{ "name": "value", "type": "uint256", "indexed": false }],
+            "anonymous": false
+        }
+    ]);
+    let instance =
+        DynamicContract::from_json(instance.address(), &abi.to_string(), instance.client())?;
+    assert_eq!(instance.event("Log(uint256)")?.inputs.len(), 1);
+    let err = instance.event("Log");
+    assert!(
+        matches!(err, Err(Error::AmbiguousOverload { ref candidates, .. }) if candidates.len() == 2)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dynamic_integer_widths() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let abi = json!([{
+        "type": "function",
+        "name": "set",
+        "inputs": [
+            { "name": "small", "type": "uint8" },
+            { "name": "delta", "type": "int8" }
+        ],
+        "outputs": [],
+        "stateMutability": "nonpayable"
+    }]);
+    let instance = DynamicContract::from_json(
+        address_from!(TEST_ADDRESS)?,
+        &abi.to_string(),
+        std::sync::Arc::new(provider),
+    )?;
+
+    let expected = instance.encode_tokens(
+        "set",
+        &[
+            Token::Uint(U256::from(255)),
+            Token::Int(I256::from(-128).into_raw()),
+        ],
+    )?;
+    for args in [[json!(255), json!(-128)], [json!("0xff"), json!("-0x80")]] {
+        assert_eq!(instance.encode("set", &args)?, expected);
+    }
+    let data = instance.encode("set", &[json!(0), json!("0x7f")])?;
+    let max =
+        instance.encode_tokens("set", &[Token::Uint(U256::zero()), Token::Int(127.into())])?;
+    assert_eq!(data, max);
+
+    for (args, name) in [
+        ([json!(300), json!(0)], "small"),
+        ([json!("0x100"), json!(0)], "small"),
+        ([json!(0), json!(-1000)], "delta"),
+        ([json!(0), json!(128)], "delta"),
+        ([json!(0), json!("-0x81")], "delta"),
+        ([json!(0), json!(format!("0x{}", "f".repeat(64)))], "delta"),
+    ] {
+        let err = instance.encode("set", &args);
+        assert!(
+            matches!(err, Err(Error::InvalidArgument(ref message)) if message.starts_with(name) && message.contains("out of range")),
+            "{args:?}: {err:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dynamic_events() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = DynamicRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let instance = registry.register_json(provider.clone(), address, &erc20_abi()?)?;
+    let owner = address_from!(TEST_OWNER)?;
+
+    let event = instance.event("Transfer")?;
+    let log = Log {
+        address,
+        topics: vec![
+            event.signature(),
+            H256::from(Address::zero()),
+            H256::from(owner),
+        ],
+        data: Bytes::from(encode(&[Token::Uint(U256::from(42))])),
+        block_number: Some(17_000_000u64.into()),
+        ..Log::default()
+    };
+    provider.as_ref().push::<Vec<Log>, _>(vec![log])?;
+
+    let events = instance
+        .events("Transfer", Filter::new().from_block(16_999_000))
+        .await?;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].name, "Transfer");
+    assert_eq!(events[0].params["to"], json!(TEST_OWNER));
+    assert_eq!(events[0].params["value"], json!("42"));
+    assert_eq!(events[0].block_number, Some(17_000_000u64.into()));
+
+    let err = instance.events("Minted", Filter::new()).await;
+    assert!(matches!(err, Err(Error::UnknownEvent(_))));
+
+    Ok(())
+}
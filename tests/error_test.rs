@@ -0,0 +1,98 @@
+use ethers::providers::{JsonRpcError, MockResponse};
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+
+async fn rpc_error(code: i64, message: &str) -> Result<Error, Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+
+    provider
+        .as_ref()
+        .push_response(MockResponse::Error(JsonRpcError {
+            code,
+            message: String::from(message),
+            data: None,
+        }));
+    let err = instance.decimals().call().await.unwrap_err();
+
+    Ok(Error::from(err))
+}
+
+#[tokio::test]
+async fn test_missing_url() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: Result<WsProvider, _> = AbiProvider::mock().provider().await;
+
+    assert!(matches!(
+        provider,
+        Err(Error::MissingUrl {
+            transport: Transport::Ws
+        })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unexpected_url() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: Result<MockProvider, _> =
+        AbiProvider::new(String::from("http://localhost:8545"), Chain::from_id(1))
+            .provider()
+            .await;
+
+    assert!(matches!(
+        provider,
+        Err(Error::UnexpectedUrl {
+            transport: Transport::Mock,
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+    let err = rpc_error(429, "Too Many Requests").await?;
+    assert!(err.is_rate_limited());
+    assert!(err.is_retryable());
+    assert!(!err.is_revert());
+
+    let err = rpc_error(-32005, "daily request count exceeded, request rate limited").await?;
+    assert!(err.is_rate_limited());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_not_retryable() -> Result<(), Box<dyn std::error::Error>> {
+    let err = rpc_error(-32602, "invalid argument 0: hex string has odd length").await?;
+
+    assert_eq!(err.as_error_response().map(|e| e.code), Some(-32602));
+    assert!(!err.is_rate_limited());
+    assert!(!err.is_retryable());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_method_not_found() -> Result<(), Box<dyn std::error::Error>> {
+    let err = rpc_error(-32601, "Method not found").await?;
+    assert!(err.is_method_not_found());
+
+    let err = rpc_error(
+        -32000,
+        "the method trace_transaction does not exist/is not available",
+    )
+    .await?;
+    assert!(err.is_method_not_found());
+
+    let err = rpc_error(-32000, "transaction not found").await?;
+    assert!(!err.is_method_not_found());
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_HTTP_PROVIDER: &str = "https://ethereum.publicnode.com";
+const TEST_ADDRESS: &str = "0x4200000000000000000000000000000000000006"; // WETH (OP stack)
+
+async fn mock_registry() -> Result<Erc20TokenMultiChainRegistry<MockProvider>, Error> {
+    let registry = Erc20TokenMultiChainRegistry::<MockProvider>::new();
+    for named in [NamedChain::Optimism, NamedChain::Base, NamedChain::Zora] {
+        let provider: MockProvider = AbiProvider::mock().provider().await?;
+        registry.add_provider(Chain::from(named), provider);
+    }
+
+    Ok(registry)
+}
+
+#[tokio::test]
+async fn test_register_across_chains() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = mock_registry().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+
+    for named in [NamedChain::Optimism, NamedChain::Base, NamedChain::Zora] {
+        let instance = registry.register(Chain::from(named), address)?;
+        assert_eq!(instance.address(), address);
+    }
+
+    let mut deployments: Vec<_> = registry
+        .deployments(address)
+        .into_iter()
+        .filter_map(|(chain, _)| chain.named())
+        .collect();
+    deployments.sort();
+    assert_eq!(
+        deployments,
+        vec![NamedChain::Optimism, NamedChain::Base, NamedChain::Zora]
+    );
+    assert_eq!(registry.entries().len(), 3);
+    assert_eq!(registry.chains().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_by_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = mock_registry().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+
+    assert!(registry.get(Chain::from_id(10), address).is_none());
+    let _instance = registry.register(Chain::from(NamedChain::Optimism), address)?;
+    assert!(registry.get(Chain::from_id(10), address).is_some());
+    assert!(registry.get(Chain::from_id(8_453), address).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unknown_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = mock_registry().await?;
+    let res = registry.register(
+        Chain::from(NamedChain::Mainnet),
+        address_from!(TEST_ADDRESS)?,
+    );
+
+    assert!(matches!(res, Err(Error::UnknownChain { chain_id: 1 })));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_chain() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenMultiChainRegistry::<HttpProvider>::new();
+    registry
+        .add_chain(TEST_HTTP_PROVIDER.into(), Chain::from(NamedChain::Mainnet))
+        .await?;
+    let instance = registry.register(
+        Chain::from(NamedChain::Mainnet),
+        address_from!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")?,
+    )?;
+
+    let _res = instance.decimals().await?;
+
+    Ok(())
+}
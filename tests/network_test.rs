@@ -41,7 +41,9 @@ async fn test_chain_from_config() -> Result<(), Box<dyn std::error::Error>> {
             timeout_retries: 2,
             initial_backoff_ms: 200,
         },
+        batch_client_config: BatchClientConfig::default(),
         assert_chain_id: true,
+        probe_capabilities: false,
     });
 
     let _provider: RetryProvider = AbiProvider::new(TEST_HTTP_PROVIDER.into(), chain)
@@ -52,6 +54,7 @@ async fn test_chain_from_config() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 #[tokio::test]
+#[allow(clippy::bool_assert_comparison)]
 async fn test_chain_from_config_default() -> Result<(), Box<dyn std::error::Error>> {
     let chain = Chain::ChainConfig(ChainConfig::default());
 
@@ -61,6 +64,7 @@ async fn test_chain_from_config_default() -> Result<(), Box<dyn std::error::Erro
 
     assert_eq!(chain.id(), 0_u64);
     assert_eq!(chain.retry_client_config(), RetryClientConfig::default());
+    assert_eq!(chain.batch_client_config(), BatchClientConfig::default());
     assert_eq!(chain.assert_chain_id(), false);
 
     Ok(())
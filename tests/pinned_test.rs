@@ -0,0 +1,195 @@
+use ethers::{
+    abi::{encode, Token},
+    contract::{
+        multicall_contract::{Call3, Multicall3},
+        MULTICALL_ADDRESS,
+    },
+    providers::{JsonRpcError, MockResponse},
+    types::{Bytes, TransactionRequest},
+};
+use ethers_abirpc::prelude::*;
+use serde_json::{json, Value};
+
+use common::{error, method, result, serve};
+
+mod common;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const OTHER_ADDRESS: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"; // USDC
+const TEST_BLOCK: u64 = 17_000_000;
+
+#[tokio::test]
+async fn test_instance_at_block() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry
+        .register(provider.clone(), address_from!(TEST_ADDRESS)?)
+        .at_block(TEST_BLOCK);
+
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(18))]));
+    provider.as_ref().push::<Bytes, _>(decimals)?;
+
+    let call = instance.decimals();
+    assert_eq!(call.call().await?, 18);
+
+    provider
+        .as_ref()
+        .assert_request("eth_call", (call.tx, BlockId::from(TEST_BLOCK)))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_at_block() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let _instance = registry.register(provider.clone(), address);
+
+    let pinned = registry.at_block(provider.clone(), TEST_BLOCK);
+    assert_eq!(pinned.block(), BlockId::from(TEST_BLOCK));
+    assert!(pinned.contains(address));
+
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(18))]));
+    provider.as_ref().push::<Bytes, _>(decimals)?;
+    let call = pinned.get(address).ok_or("missing entry")?.decimals();
+    assert_eq!(call.call().await?, 18);
+    provider
+        .as_ref()
+        .assert_request("eth_call", (call.tx, BlockId::from(TEST_BLOCK)))?;
+
+    let other = address_from!(OTHER_ADDRESS)?;
+    let instance = pinned.register(other);
+    provider.as_ref().push::<U256, _>(U256::from(100))?;
+    let balance = instance.client().get_balance(other, None).await?;
+    assert_eq!(balance, U256::from(100));
+
+    provider
+        .as_ref()
+        .assert_request("eth_getBalance", (other, BlockId::from(TEST_BLOCK)))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_registry_at_block_multicall() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let _instance = registry.register(provider.clone(), address);
+
+    let pinned = registry.at_block(provider.clone(), TEST_BLOCK);
+    let decimals = pinned.get(address).ok_or("missing entry")?.decimals();
+    let mut multicall = pinned.multicall(Some(MULTICALL_ADDRESS)).await?;
+    multicall.add_call(decimals.clone(), false);
+
+    let results = Token::Array(vec![Token::Tuple(vec![
+        Token::Bool(true),
+        Token::Bytes(encode(&[Token::Uint(U256::from(18))])),
+    ])]);
+    provider
+        .as_ref()
+        .push::<Bytes, _>(Bytes::from(encode(&[results])))?;
+    let (res,): (u8,) = multicall.call().await?;
+    assert_eq!(res, 18);
+
+    let aggregate = Multicall3::new(MULTICALL_ADDRESS, pinned.client()).aggregate_3(vec![Call3 {
+        target: address,
+        allow_failure: false,
+        call_data: decimals.calldata().ok_or("missing calldata")?,
+    }]);
+    provider
+        .as_ref()
+        .assert_request("eth_call", (aggregate.tx, BlockId::from(TEST_BLOCK)))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_explicit_block_not_overridden() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let pinned = BlockPinned::new(provider.clone(), TEST_BLOCK);
+
+    let tx = TransactionRequest::new().into();
+    provider.as_ref().push::<Bytes, _>(Bytes::new())?;
+    let _res = pinned.call(&tx, Some(BlockId::from(1_u64))).await?;
+
+    provider
+        .as_ref()
+        .assert_request("eth_call", (tx, BlockId::from(1_u64)))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_archive_node_required() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let pinned = BlockPinned::new(provider.clone(), TEST_BLOCK);
+
+    provider
+        .as_ref()
+        .push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: String::from(
+                "missing trie node 1a2b3c (path ) state 0x1a2b3c is not available",
+            ),
+            data: None,
+        }));
+
+    let res = pinned.check_archive().await;
+    assert!(matches!(
+        res,
+        Err(Error::ArchiveNodeRequired {
+            block: BlockId::Number(BlockNumber::Number(_)),
+            ..
+        })
+    ));
+
+    Ok(())
+}
+
+/// Node which has pruned the state of every block.
+fn pruned(request: &Value) -> Value {
+    match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        _ => error(-32000, "missing trie node 1a2b3c (path )"),
+    }
+}
+
+#[tokio::test]
+async fn test_pinned_calls_archive_node_required() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, _) = serve(pruned).await?;
+    let registry = Erc20TokenRegistry::<HttpProvider>::new(url, Chain::from_id(1));
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+
+    let res = registry
+        .register(provider.clone(), address)
+        .at_block(TEST_BLOCK)
+        .decimals()
+        .call()
+        .await
+        .map_err(Error::from);
+    assert!(matches!(
+        res,
+        Err(Error::ArchiveNodeRequired { block, .. }) if block == BlockId::from(TEST_BLOCK)
+    ));
+
+    let pinned = registry.at_block(provider, TEST_BLOCK);
+    let mut multicall = pinned.multicall(Some(MULTICALL_ADDRESS)).await?;
+    multicall.add_call(
+        pinned.get(address).ok_or("missing entry")?.decimals(),
+        false,
+    );
+
+    let res = multicall.call::<(u8,)>().await.map_err(Error::from);
+    assert!(matches!(
+        res,
+        Err(Error::ArchiveNodeRequired { block, .. }) if block == BlockId::from(TEST_BLOCK)
+    ));
+
+    Ok(())
+}
@@ -54,6 +54,15 @@ async fn test_retry_client() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_batch() -> Result<(), Box<dyn std::error::Error>> {
+    let _provider: BatchProvider = AbiProvider::new(TEST_HTTP_PROVIDER.into(), TEST_CHAIN)
+        .provider()
+        .await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_mock_provider() -> Result<(), Box<dyn std::error::Error>> {
     let _provider: MockProvider = AbiProvider::mock().provider().await?;
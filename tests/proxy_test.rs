@@ -0,0 +1,186 @@
+use ethers::{
+    abi::{encode, Token},
+    providers::{JsonRpcError, MockResponse},
+    types::{Address, Bytes, H256},
+};
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_PROXY: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"; // USDC
+const TEST_IMPLEMENTATION: &str = "0x43506849D7C04F9138D1A2050bbF3A0c054402dd";
+const TEST_UPGRADE: &str = "0x0882477e7895bdC5cea7cB1552ed914aB157Fe56";
+const TEST_ADMIN: &str = "0x807a96288A1A408dBC13DE2b1d087d10356395d2";
+const TEST_BEACON: &str = "0x5a52E96BAcdaBb82fd05763E25335261B270Efcb";
+
+fn code() -> Bytes {
+    Bytes::from(vec![0x60, 0x80, 0x60, 0x40, 0x52])
+}
+
+fn slot(address: Address) -> H256 {
+    H256::from(address)
+}
+
+// MockProvider responses are returned in reverse order
+fn push_eip1967(
+    provider: &MockProvider,
+    implementation: Address,
+) -> Result<(), Box<dyn std::error::Error>> {
+    provider
+        .as_ref()
+        .push::<H256, _>(slot(address_from!(TEST_ADMIN)?))?;
+    provider.as_ref().push::<H256, _>(slot(implementation))?;
+    provider.as_ref().push::<Bytes, _>(code())?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_eip1967_proxy() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_PROXY)?;
+    let implementation = address_from!(TEST_IMPLEMENTATION)?;
+
+    push_eip1967(&provider, implementation)?;
+    let (_instance, proxy) = registry.register_proxy(provider.clone(), address).await?;
+
+    let expected = ProxyInfo {
+        kind: ProxyKind::Eip1967,
+        implementation,
+        admin: Some(address_from!(TEST_ADMIN)?),
+        beacon: None,
+    };
+    assert_eq!(proxy, Some(expected));
+
+    let metadata = registry.metadata(address).unwrap_or_default();
+    assert_eq!(metadata.proxy, Some(ProxyKind::Eip1967));
+    assert_eq!(metadata.implementation, Some(implementation));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_beacon_proxy() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let implementation = address_from!(TEST_IMPLEMENTATION)?;
+    let beacon = address_from!(TEST_BEACON)?;
+
+    let output = Bytes::from(encode(&[Token::Address(implementation)]));
+    provider.as_ref().push::<Bytes, _>(output)?;
+    provider.as_ref().push::<H256, _>(slot(beacon))?;
+    provider.as_ref().push::<H256, _>(H256::zero())?;
+    provider.as_ref().push::<Bytes, _>(code())?;
+
+    let (_instance, proxy) = registry
+        .register_proxy(provider.clone(), address_from!(TEST_PROXY)?)
+        .await?;
+    assert_eq!(
+        proxy.map(|proxy| proxy.kind),
+        Some(ProxyKind::Eip1967Beacon)
+    );
+    assert_eq!(proxy.and_then(|proxy| proxy.beacon), Some(beacon));
+    assert_eq!(
+        proxy.map(|proxy| proxy.implementation),
+        Some(implementation)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_minimal_proxy() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let implementation = address_from!(TEST_IMPLEMENTATION)?;
+
+    let mut code = vec![0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+    code.extend_from_slice(implementation.as_bytes());
+    code.extend_from_slice(&[
+        0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+    ]);
+    provider.as_ref().push::<Bytes, _>(Bytes::from(code))?;
+
+    let (_instance, proxy) = registry
+        .register_proxy(provider.clone(), address_from!(TEST_PROXY)?)
+        .await?;
+    assert_eq!(proxy.map(|proxy| proxy.kind), Some(ProxyKind::Eip1167));
+    assert_eq!(
+        proxy.map(|proxy| proxy.implementation),
+        Some(implementation)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_not_a_proxy() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_PROXY)?;
+
+    for _ in 0..3 {
+        provider.as_ref().push::<H256, _>(H256::zero())?;
+    }
+    provider.as_ref().push::<Bytes, _>(code())?;
+
+    let (_instance, proxy) = registry.register_proxy(provider.clone(), address).await?;
+    assert!(proxy.is_none());
+    assert!(registry.contains(address));
+    assert!(registry.check_implementations().await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proxy_detection_error() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_PROXY)?;
+
+    provider
+        .as_ref()
+        .push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: String::from("header not found"),
+            data: None,
+        }));
+    let result = registry.register_proxy(provider.clone(), address).await;
+    assert!(result.is_err());
+    assert!(!registry.contains(address));
+    assert!(registry.metadata(address).is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_implementation_change() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_PROXY)?;
+    let implementation = address_from!(TEST_IMPLEMENTATION)?;
+    let upgrade = address_from!(TEST_UPGRADE)?;
+
+    push_eip1967(&provider, implementation)?;
+    registry.register_proxy(provider.clone(), address).await?;
+
+    push_eip1967(&provider, implementation)?;
+    assert!(registry.check_implementation(address).await?.is_none());
+
+    push_eip1967(&provider, upgrade)?;
+    let changes = registry.check_implementations().await?;
+    let expected = ImplementationChange {
+        address,
+        previous: Some(implementation),
+        current: Some(upgrade),
+    };
+    assert_eq!(changes, vec![expected]);
+    assert_eq!(
+        registry.metadata(address).and_then(|m| m.implementation),
+        Some(upgrade)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use ethers::{
+    abi::{encode, Token},
+    types::Bytes,
+};
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+
+/// Erc20 registry limited to the http and mock transports
+#[abirpc_registry(Erc20Token, transports(Http, Mock), multichain = TokenMultiChainRegistry)]
+pub struct TokenRegistry;
+
+// Contract helpers are generated by the first registry
+#[abirpc_registry(Erc20Token, transports(Mock), helpers = false)]
+struct MockTokenRegistry;
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+
+#[tokio::test]
+async fn test_attribute_registry() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+
+    let decimals = Bytes::from(encode(&[Token::Uint(U256::from(18))]));
+    provider.as_ref().push::<Bytes, _>(decimals)?;
+    assert_eq!(instance.decimals().call().await?, 18);
+
+    let pinned = instance.at_block(17_000_000);
+    assert_eq!(pinned.address(), instance.address());
+
+    let registry = TokenRegistry::<HttpProvider>::new(String::new(), Chain::Id(1));
+    assert!(registry.is_empty());
+
+    let multichain = TokenMultiChainRegistry::<MockProvider>::new();
+    multichain.add_provider(Chain::Id(1), provider);
+    assert_eq!(multichain.chains(), vec![Chain::Id(1)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_attribute_registry_without_helpers() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = MockTokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    registry.register(provider, address_from!(TEST_ADDRESS)?);
+    assert_eq!(registry.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
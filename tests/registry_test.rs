@@ -42,6 +42,19 @@ async fn test_retry_client() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_batch() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<BatchProvider>::new(TEST_HTTP_PROVIDER.into(), TEST_CHAIN);
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider, address_from!(TEST_ADDRESS)?);
+
+    let (decimals, symbol) = (instance.decimals(), instance.symbol());
+    let (decimals, symbol) = tokio::join!(decimals.call(), symbol.call());
+    let _res = (decimals?, symbol?);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_mock_provider() -> Result<(), Box<dyn std::error::Error>> {
     let registry = Erc20TokenRegistry::<MockProvider>::mock();
@@ -74,3 +87,47 @@ async fn test_get_logs() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_registry_crud() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let weth = address_from!(TEST_ADDRESS)?;
+    let wbtc = address_from!("0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599")?;
+
+    assert!(registry.is_empty());
+    assert!(registry.get(weth).is_none());
+
+    let _weth = registry.register(provider.clone(), weth);
+    let _wbtc = registry.register(provider.clone(), wbtc);
+    let _weth = registry.register(provider, weth);
+
+    assert_eq!(registry.len(), 2);
+    assert!(registry.contains(weth));
+    assert_eq!(
+        registry.get(weth).map(|instance| instance.address()),
+        Some(weth)
+    );
+
+    let mut addresses = registry.addresses();
+    addresses.sort();
+    let mut expected = vec![weth, wbtc];
+    expected.sort();
+    assert_eq!(addresses, expected);
+
+    for (address, instance) in registry.entries() {
+        assert_eq!(address, instance.address());
+    }
+
+    assert_eq!(
+        registry.unregister(weth).map(|instance| instance.address()),
+        Some(weth)
+    );
+    assert!(registry.unregister(weth).is_none());
+    assert_eq!(registry.len(), 1);
+
+    registry.clear();
+    assert!(registry.is_empty());
+
+    Ok(())
+}
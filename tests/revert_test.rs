@@ -0,0 +1,110 @@
+use ethers::{
+    abi::{encode, Token},
+    contract::EthError,
+    providers::{JsonRpcError, MockResponse},
+    types::Bytes,
+};
+use ethers_abirpc::prelude::*;
+
+abigen!(Vault, "./tests/abi/Vault.json");
+abirpc!(Vault);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+
+fn revert_data(selector: [u8; 4], tokens: &[Token]) -> Bytes {
+    Bytes::from([&selector[..], &encode(tokens)].concat())
+}
+
+fn revert_response(data: Bytes) -> MockResponse {
+    MockResponse::Error(JsonRpcError {
+        code: 3,
+        message: String::from("execution reverted"),
+        data: Some(serde_json::to_value(data).unwrap()),
+    })
+}
+
+async fn withdraw(response: MockResponse) -> Result<Error, Box<dyn std::error::Error>> {
+    let registry = VaultRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+
+    provider.as_ref().push_response(response);
+    let err = instance.withdraw(U256::from(100)).call().await.unwrap_err();
+
+    Ok(Error::from(err))
+}
+
+#[tokio::test]
+async fn test_revert_error_string() -> Result<(), Box<dyn std::error::Error>> {
+    let response = revert_response(revert_data(
+        [0x08, 0xc3, 0x79, 0xa0],
+        &[Token::String(String::from("Vault: paused"))],
+    ));
+    let err = withdraw(response).await?;
+
+    let revert = err.as_revert().expect("revert");
+    assert_eq!(
+        revert.reason,
+        RevertReason::Error(String::from("Vault: paused"))
+    );
+    assert_eq!(revert.selector(), Some([0x08, 0xc3, 0x79, 0xa0]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revert_panic() -> Result<(), Box<dyn std::error::Error>> {
+    let response = revert_response(revert_data(
+        [0x4e, 0x48, 0x7b, 0x71],
+        &[Token::Uint(U256::from(0x11))],
+    ));
+    let err = withdraw(response).await?;
+
+    let revert = err.as_revert().expect("revert");
+    assert_eq!(
+        revert.reason,
+        RevertReason::Panic(PanicCode::ArithmeticOverflow)
+    );
+    assert_eq!(
+        revert.reason.to_string(),
+        "Panic(0x11 (arithmetic overflow or underflow))"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revert_custom_error() -> Result<(), Box<dyn std::error::Error>> {
+    let selector = <InsufficientBalance as EthError>::selector();
+    let response = revert_response(revert_data(
+        selector,
+        &[Token::Uint(U256::from(10)), Token::Uint(U256::from(100))],
+    ));
+    let err = withdraw(response).await?;
+
+    assert_eq!(
+        err.as_revert().map(|revert| revert.reason.clone()),
+        Some(RevertReason::Custom(selector))
+    );
+    assert_eq!(
+        err.decode_contract_revert::<VaultErrors>(),
+        Some(VaultErrors::InsufficientBalance(InsufficientBalance {
+            available: U256::from(10),
+            required: U256::from(100),
+        }))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_revert_empty() -> Result<(), Box<dyn std::error::Error>> {
+    let err = withdraw(revert_response(Bytes::new())).await?;
+
+    assert_eq!(
+        err.as_revert().map(|revert| revert.reason.clone()),
+        Some(RevertReason::Empty)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use ethers::{
+    abi::{encode, Token},
+    providers::{JsonRpcError, MockResponse},
+    types::{Bytes, H256, U64},
+};
+use ethers_abirpc::prelude::*;
+use serde_json::{json, Value};
+
+use common::{error, method, requested, result, serve};
+
+mod common;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_HOLDER: &str = "0x000000000000000000000000000000000000dEaD";
+const TEST_BLOCK: u64 = 17_000_000;
+const GAS_USED: u64 = 34_500;
+const GAS_ESTIMATE: u64 = 51_000;
+
+/// Top frame of a `callTracer` trace reporting `GAS_USED`.
+fn call_frame() -> Value {
+    json!({
+        "type": "CALL",
+        "from": TEST_HOLDER,
+        "to": TEST_ADDRESS,
+        "gas": format!("{GAS_ESTIMATE:#x}"),
+        "gasUsed": format!("{GAS_USED:#x}"),
+        "input": "0x",
+    })
+}
+
+fn trace_config(overrides: &Overrides) -> Value {
+    let mut config = json!({ "tracer": "callTracer", "stateOverrides": overrides.state });
+    if let Some(block) = &overrides.block {
+        config["blockOverrides"] = json!(block);
+    }
+    config
+}
+
+#[tokio::test]
+async fn test_simulate_with_overrides() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+    let holder = address_from!(TEST_HOLDER)?;
+
+    let mut overrides = Overrides::default();
+    overrides
+        .account(instance.address())
+        .store(H256::zero(), H256::from_low_u64_be(1));
+    overrides.account(holder).balance(U256::exp10(18));
+    overrides.block = Some(BlockOverrides {
+        number: Some(U64::from(20_000_000)),
+        ..BlockOverrides::default()
+    });
+
+    // MockProvider responses are returned in reverse order
+    provider.as_ref().push::<Value, _>(call_frame())?;
+    let transfer = Bytes::from(encode(&[Token::Bool(true)]));
+    provider.as_ref().push::<Bytes, _>(transfer)?;
+
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance.simulate(call.clone(), &overrides).await?;
+
+    assert!(simulation.output);
+    assert_eq!(simulation.gas_used, U256::from(GAS_USED));
+
+    let block = BlockId::from(BlockNumber::Latest);
+    let config = trace_config(&overrides);
+    let params = (&call.tx, block, &overrides.state, &overrides.block);
+    provider.as_ref().assert_request("eth_call", params)?;
+    provider
+        .as_ref()
+        .assert_request("debug_traceCall", (&call.tx, block, config))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_simulate_at_pinned_block() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry
+        .register(provider.clone(), address_from!(TEST_ADDRESS)?)
+        .at_block(TEST_BLOCK);
+    let holder = address_from!(TEST_HOLDER)?;
+
+    let mut overrides = Overrides::default();
+    overrides.account(holder).balance(U256::exp10(18));
+
+    provider.as_ref().push::<Value, _>(call_frame())?;
+    let transfer = Bytes::from(encode(&[Token::Bool(true)]));
+    provider.as_ref().push::<Bytes, _>(transfer)?;
+
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance.simulate(call.clone(), &overrides).await?;
+    assert!(simulation.output);
+
+    let block = BlockId::from(TEST_BLOCK);
+    let config = trace_config(&overrides);
+    provider
+        .as_ref()
+        .assert_request("eth_call", (&call.tx, block, &overrides.state))?;
+    provider
+        .as_ref()
+        .assert_request("debug_traceCall", (&call.tx, block, config))?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_simulate_without_overrides() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry
+        .register(provider.clone(), address_from!(TEST_ADDRESS)?)
+        .at_block(TEST_BLOCK);
+    let holder = address_from!(TEST_HOLDER)?;
+
+    let access_list = json!({ "accessList": [], "gasUsed": format!("{GAS_USED:#x}") });
+    provider.as_ref().push::<Value, _>(access_list)?;
+    let transfer = Bytes::from(encode(&[Token::Bool(true)]));
+    provider.as_ref().push::<Bytes, _>(transfer)?;
+
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance
+        .simulate(call.clone(), &Overrides::default())
+        .await?;
+    assert!(simulation.output);
+    assert_eq!(simulation.gas_used, U256::from(GAS_USED));
+
+    // Sent through the BlockPinned middleware
+    let params = (call.tx, BlockId::from(TEST_BLOCK));
+    provider.as_ref().assert_request("eth_call", &params)?;
+    provider
+        .as_ref()
+        .assert_request("eth_createAccessList", &params)?;
+
+    Ok(())
+}
+
+/// Node reporting more gas from `eth_estimateGas` than the call uses.
+fn node(request: &Value) -> Value {
+    match method(request) {
+        "eth_chainId" => result(json!("0x1")),
+        "eth_call" => result(json!(Bytes::from(encode(&[Token::Bool(true)])))),
+        "eth_createAccessList" => {
+            result(json!({ "accessList": [], "gasUsed": format!("{GAS_USED:#x}") }))
+        }
+        "eth_estimateGas" => result(json!(format!("{GAS_ESTIMATE:#x}"))),
+        _ => error(-32601, "Method not found"),
+    }
+}
+
+#[tokio::test]
+async fn test_simulate_gas_used() -> Result<(), Box<dyn std::error::Error>> {
+    let (url, requests) = serve(node).await?;
+    let registry = Erc20TokenRegistry::<HttpProvider>::new(url, Chain::from_id(1));
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider, address_from!(TEST_ADDRESS)?);
+    let holder = address_from!(TEST_HOLDER)?;
+
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance.simulate(call, &Overrides::default()).await?;
+    assert_eq!(simulation.gas_used, U256::from(GAS_USED));
+    assert!(requested(&requests, "eth_estimateGas").is_empty());
+
+    // Without debug_traceCall the estimate is the only gas figure available
+    let mut overrides = Overrides::default();
+    overrides.account(holder).balance(U256::exp10(18));
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance.simulate(call, &overrides).await?;
+    assert_eq!(simulation.gas_used, U256::from(GAS_ESTIMATE));
+    assert_eq!(requested(&requests, "debug_traceCall").len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_simulate_unsupported_access_list() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let instance = registry.register(provider.clone(), address_from!(TEST_ADDRESS)?);
+    let holder = address_from!(TEST_HOLDER)?;
+
+    provider
+        .as_ref()
+        .push::<U256, _>(U256::from(GAS_ESTIMATE))?;
+    provider
+        .as_ref()
+        .push_response(MockResponse::Error(JsonRpcError {
+            code: -32601,
+            message: String::from("the method eth_createAccessList does not exist"),
+            data: None,
+        }));
+    let transfer = Bytes::from(encode(&[Token::Bool(true)]));
+    provider.as_ref().push::<Bytes, _>(transfer)?;
+
+    let call = instance.transfer(holder, U256::from(1)).from(holder);
+    let simulation = instance.simulate(call, &Overrides::default()).await?;
+    assert_eq!(simulation.gas_used, U256::from(GAS_ESTIMATE));
+
+    Ok(())
+}
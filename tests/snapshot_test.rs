@@ -0,0 +1,111 @@
+use ethers::providers::Provider;
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+abigen!(Erc721Token, "./tests/abi/Erc721Token.json");
+abirpc!(Erc721Token);
+
+const TEST_CHAIN: Chain = Chain::Id(1);
+const TEST_WETH: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+const TEST_USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+fn populate(
+    registry: &Erc20TokenRegistry<MockProvider>,
+    provider: MockProvider,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let weth = address_from!(TEST_WETH)?;
+    registry.register_as(provider.clone(), "WETH", weth);
+    registry.set_metadata(
+        weth,
+        EntryMetadata {
+            deployment_block: Some(4_719_568),
+            ..EntryMetadata::default()
+        },
+    );
+    registry.register(provider, address_from!(TEST_USDC)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = Provider::new(ethers::providers::MockProvider::new());
+    let registry = Erc20TokenRegistry::<MockProvider>::new(String::new(), TEST_CHAIN);
+    populate(&registry, provider.clone())?;
+
+    let snapshot = registry.snapshot();
+    assert_eq!(snapshot.abi, "Erc20Token");
+    assert_eq!(snapshot.chain_id, Some(1));
+    assert_eq!(snapshot.entries.len(), 2);
+
+    assert_eq!(RegistrySnapshot::from_json(&snapshot.to_json()?)?, snapshot);
+    assert_eq!(RegistrySnapshot::from_toml(&snapshot.to_toml()?)?, snapshot);
+
+    let restored = Erc20TokenRegistry::<MockProvider>::new(String::new(), TEST_CHAIN);
+    let instances = restored.restore(provider, &snapshot)?;
+
+    let weth = address_from!(TEST_WETH)?;
+    assert_eq!(instances.len(), 2);
+    assert_eq!(restored.resolve("WETH"), Some(weth));
+    assert_eq!(
+        restored.metadata(weth).and_then(|m| m.deployment_block),
+        Some(4_719_568)
+    );
+    assert!(restored.metadata(address_from!(TEST_USDC)?).is_none());
+    assert_eq!(restored.snapshot(), snapshot);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_file() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = Provider::new(ethers::providers::MockProvider::new());
+    let registry = Erc20TokenRegistry::<MockProvider>::new(String::new(), TEST_CHAIN);
+    populate(&registry, provider)?;
+
+    let snapshot = registry.snapshot();
+    let dir = std::env::temp_dir();
+    for name in ["abirpc_snapshot.json", "abirpc_snapshot.toml"] {
+        let path = dir.join(name);
+        snapshot.to_file(&path)?;
+        assert_eq!(RegistrySnapshot::from_file(&path)?, snapshot);
+        std::fs::remove_file(path)?;
+    }
+
+    let err = snapshot
+        .to_file(dir.join("abirpc_snapshot.yaml"))
+        .unwrap_err();
+    assert!(matches!(err, Error::UnsupportedFormat(_)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_snapshot_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = Provider::new(ethers::providers::MockProvider::new());
+    let registry = Erc20TokenRegistry::<MockProvider>::new(String::new(), TEST_CHAIN);
+    populate(&registry, provider.clone())?;
+    let snapshot = registry.snapshot();
+
+    let nft_registry = Erc721TokenRegistry::<MockProvider>::mock();
+    let err = nft_registry
+        .restore(provider.clone(), &snapshot)
+        .unwrap_err();
+    assert!(matches!(err, Error::AbiMismatch { .. }));
+
+    let other_chain = Erc20TokenRegistry::<MockProvider>::new(String::new(), Chain::Id(10));
+    let err = other_chain
+        .restore(provider.clone(), &snapshot)
+        .unwrap_err();
+    assert!(matches!(err, Error::ChainIdMismatch { expected: 10, .. }));
+    assert!(other_chain.is_empty());
+
+    // Registries without a chain accept snapshots from any chain
+    let mock = Erc20TokenRegistry::<MockProvider>::mock();
+    mock.restore(provider, &snapshot)?;
+    assert_eq!(mock.len(), 2);
+
+    Ok(())
+}
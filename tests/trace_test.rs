@@ -0,0 +1,177 @@
+use ethers::{
+    abi::{encode, parse_abi, AbiEncode, Token},
+    types::{Bytes, Trace},
+};
+use ethers_abirpc::prelude::*;
+use serde_json::json;
+use std::sync::Arc;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const TEST_OWNER: &str = "0x000000000000000000000000000000000000dEaD";
+const TEST_VAULT: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+const TEST_UNKNOWN: &str = "0x1111111111111111111111111111111111111111";
+
+fn decoder() -> Result<TxDecoder, Box<dyn std::error::Error>> {
+    let decoder = TxDecoder::new();
+    decoder.insert(
+        address_from!(TEST_ADDRESS)?,
+        "Erc20Token",
+        Arc::new(ERC20TOKEN_ABI.clone()),
+    );
+    let vault = parse_abi(&[
+        "function withdraw(uint256 amount)",
+        "error InsufficientBalance(uint256 available)",
+    ])?;
+    decoder.insert(address_from!(TEST_VAULT)?, "Vault", Arc::new(vault));
+
+    Ok(decoder)
+}
+
+fn transfer_input() -> Result<Bytes, Box<dyn std::error::Error>> {
+    let call = TransferCall {
+        to: address_from!(TEST_OWNER)?,
+        value: U256::from(42),
+    };
+
+    Ok(Bytes::from(call.encode()))
+}
+
+#[tokio::test]
+async fn test_debug_trace() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let decoder = decoder()?;
+
+    // InsufficientBalance(7)
+    let mut custom = ethers::utils::id("InsufficientBalance(uint256)").to_vec();
+    custom.extend(encode(&[Token::Uint(U256::from(7))]));
+    let frame = json!({
+        "type": "CALL",
+        "from": TEST_OWNER,
+        "to": TEST_ADDRESS,
+        "gas": "0x10000",
+        "gasUsed": "0x5208",
+        "input": transfer_input()?,
+        "output": Bytes::from(encode(&[Token::Bool(true)])),
+        "calls": [
+            {
+                "type": "STATICCALL",
+                "from": TEST_ADDRESS,
+                "to": TEST_UNKNOWN,
+                "gas": "0x100",
+                "gasUsed": "0x10",
+                "input": "0xdeadbeef",
+                "output": "0x01"
+            },
+            {
+                "type": "DELEGATECALL",
+                "from": TEST_ADDRESS,
+                "to": TEST_VAULT,
+                "gas": "0x100",
+                "gasUsed": "0x10",
+                "input": "0x2e1a7d4d000000000000000000000000000000000000000000000000000000000000000a",
+                "output": Bytes::from(custom),
+                "error": "execution reverted"
+            }
+        ]
+    });
+    provider.as_ref().push(frame)?;
+
+    let trace = decoder
+        .debug_trace_transaction(&provider, Default::default())
+        .await?;
+    assert_eq!(trace.contract.as_deref(), Some("Erc20Token"));
+    assert_eq!(trace.function.as_deref(), Some("transfer"));
+    assert_eq!(
+        trace.args.as_ref().map(|args| &args["value"]),
+        Some(&json!("42"))
+    );
+    assert_eq!(trace.output, Some(json!(true)));
+    assert_eq!(trace.calls.len(), 2);
+
+    let unknown = &trace.calls[0];
+    assert_eq!(unknown.function, None);
+    assert_eq!(unknown.selector.as_deref(), Some("0xdeadbeef"));
+    assert_eq!(unknown.output, Some(json!("0x01")));
+
+    let reverted = &trace.calls[1];
+    assert_eq!(reverted.kind, "DELEGATECALL");
+    assert_eq!(reverted.function.as_deref(), Some("withdraw"));
+    assert_eq!(
+        reverted.revert.as_deref(),
+        Some("InsufficientBalance(\"7\")")
+    );
+
+    let rendered = trace.to_string();
+    assert!(rendered.starts_with("CALL Erc20Token("));
+    assert!(
+        rendered.contains("\n  STATICCALL 0x1111111111111111111111111111111111111111::0xdeadbeef")
+    );
+    assert!(rendered
+        .contains(".withdraw(amount: \"10\") !! execution reverted: InsufficientBalance(\"7\")"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parity_trace() -> Result<(), Box<dyn std::error::Error>> {
+    let provider: MockProvider = AbiProvider::mock().provider().await?;
+    let decoder = decoder()?;
+
+    let trace = |trace_address: Vec<usize>, from: &str, to: &str, input: Bytes| {
+        json!({
+            "action": {
+                "from": from,
+                "to": to,
+                "value": "0x0",
+                "gas": "0x100",
+                "input": input,
+                "callType": "call"
+            },
+            "result": { "gasUsed": "0x10", "output": "0x" },
+            "traceAddress": trace_address,
+            "subtraces": 0,
+            "transactionPosition": 0,
+            "transactionHash": format!("{:?}", ethers::types::H256::zero()),
+            "blockNumber": 1,
+            "blockHash": format!("{:?}", ethers::types::H256::zero()),
+            "type": "call"
+        })
+    };
+    let traces: Vec<Trace> = serde_json::from_value(json!([
+        trace(vec![], TEST_OWNER, TEST_ADDRESS, transfer_input()?),
+        trace(
+            vec![1],
+            TEST_ADDRESS,
+            TEST_UNKNOWN,
+            Bytes::from(vec![0xca, 0xfe, 0xba, 0xbe])
+        ),
+        trace(
+            vec![0],
+            TEST_ADDRESS,
+            TEST_VAULT,
+            Bytes::from(vec![0x2e, 0x1a, 0x7d, 0x4d])
+        ),
+    ]))?;
+    provider.as_ref().push::<Vec<Trace>, _>(traces)?;
+
+    let trace = decoder
+        .trace_transaction(&provider, Default::default())
+        .await?;
+    assert_eq!(trace.function.as_deref(), Some("transfer"));
+    assert_eq!(trace.calls.len(), 2);
+    assert_eq!(trace.calls[0].contract.as_deref(), Some("Vault"));
+    assert_eq!(trace.calls[0].function.as_deref(), Some("withdraw"));
+    assert_eq!(trace.calls[0].args, None); // Truncated calldata
+    assert_eq!(trace.calls[1].selector.as_deref(), Some("0xcafebabe"));
+
+    provider.as_ref().push::<Vec<Trace>, _>(vec![])?;
+    let err = decoder
+        .trace_transaction(&provider, Default::default())
+        .await;
+    assert!(matches!(err, Err(Error::TransactionNotFound(_))));
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+use ethers_abirpc::prelude::*;
+
+abirpc_dir!("./contracts/abi");
+
+fn main() {}
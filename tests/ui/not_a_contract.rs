@@ -0,0 +1,8 @@
+use ethers_abirpc::prelude::*;
+
+pub struct Erc20Token;
+
+#[abirpc_registry(Erc20Token, transports(Mock))]
+pub struct TokenRegistry;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, r#"[function decimals() external view returns (uint8)]"#);
+
+#[abirpc_registry(Erc20Token)]
+pub struct TokenRegistry {
+    url: String,
+}
+
+fn main() {}
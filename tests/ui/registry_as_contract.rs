@@ -0,0 +1,6 @@
+use ethers_abirpc::prelude::*;
+
+#[abirpc_registry(TokenRegistry, transports(Mock))]
+pub struct TokenRegistry;
+
+fn main() {}
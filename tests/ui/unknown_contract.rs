@@ -0,0 +1,8 @@
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, r#"[function decimals() external view returns (uint8)]"#);
+
+#[abirpc_registry(Erc20Tokn, transports(Mock))]
+pub struct TokenRegistry;
+
+fn main() {}
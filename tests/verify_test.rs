@@ -0,0 +1,150 @@
+use ethers::{
+    abi::{encode, Token},
+    providers::{JsonRpcError, MockResponse},
+    types::Bytes,
+};
+use ethers_abirpc::prelude::*;
+
+abigen!(Erc20Token, "./tests/abi/Erc20Token.json");
+abirpc!(Erc20Token);
+
+const TEST_ADDRESS: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"; // WETH
+const ERC20_INTERFACE: [u8; 4] = [0x36, 0x37, 0x2b, 0x07];
+
+// Minimal dispatcher pushing each selector with the shortest PUSH
+fn dispatcher(selectors: &[[u8; 4]]) -> Bytes {
+    let mut code = vec![0x60, 0x80, 0x60, 0x40, 0x52];
+    for selector in selectors {
+        let start = selector.iter().position(|b| *b != 0).unwrap_or(3);
+        code.push(0x60 + (3 - start) as u8);
+        code.extend_from_slice(&selector[start..]);
+        code.extend_from_slice(&[0x14, 0x61, 0x00, 0x00, 0x57]);
+    }
+    Bytes::from(code)
+}
+
+fn erc20_selectors() -> Vec<[u8; 4]> {
+    ERC20TOKEN_ABI
+        .functions()
+        .map(|function| function.short_signature())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_verify_no_code() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+
+    provider.as_ref().push::<Bytes, _>(Bytes::new())?;
+    let err = registry
+        .register_verified(provider.clone(), address, &Verification::new())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, Error::NoCode { .. }));
+    assert!(!registry.contains(address));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_selectors() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let verification = Verification::new().abi_selectors();
+
+    let mut selectors = erc20_selectors();
+    selectors.retain(|selector| *selector != [0xa9, 0x05, 0x9c, 0xbb]); // transfer
+    provider.as_ref().push::<Bytes, _>(dispatcher(&selectors))?;
+    let err = registry
+        .register_verified(provider.clone(), address, &verification)
+        .await
+        .unwrap_err();
+
+    match err {
+        Error::MissingSelectors { selectors, .. } => {
+            assert_eq!(selectors, vec!["transfer(address,uint256)"])
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+    assert!(!registry.contains(address));
+
+    provider
+        .as_ref()
+        .push::<Bytes, _>(dispatcher(&erc20_selectors()))?;
+    registry
+        .register_verified(provider.clone(), address, &verification)
+        .await?;
+    assert!(registry.contains(address));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_leading_zero_selector() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let selector = [0x00, 0xfd, 0xd5, 0x8e];
+    let verification = Verification::new().selectors([selector]);
+
+    provider
+        .as_ref()
+        .push::<Bytes, _>(dispatcher(&[selector]))?;
+    registry
+        .register_verified(
+            provider.clone(),
+            address_from!(TEST_ADDRESS)?,
+            &verification,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_interface() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = Erc20TokenRegistry::<MockProvider>::mock();
+    let provider = registry.provider().await?;
+    let address = address_from!(TEST_ADDRESS)?;
+    let verification = Verification::new().interface(ERC20_INTERFACE);
+    let code = dispatcher(&erc20_selectors());
+
+    // MockProvider responses are returned in reverse order
+    provider
+        .as_ref()
+        .push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: String::from("execution reverted"),
+            data: Some(serde_json::Value::String(String::from("0x"))),
+        }));
+    provider.as_ref().push::<Bytes, _>(code.clone())?;
+    let err = registry
+        .register_verified(provider.clone(), address, &verification)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InterfaceNotSupported { .. }));
+
+    let unsupported = Bytes::from(encode(&[Token::Bool(false)]));
+    provider.as_ref().push::<Bytes, _>(unsupported)?;
+    provider.as_ref().push::<Bytes, _>(code.clone())?;
+    let err = registry
+        .register_verified(provider.clone(), address, &verification)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Contract at {address:?} does not support interface 0x36372b07")
+    );
+
+    let supported = Bytes::from(encode(&[Token::Bool(true)]));
+    provider.as_ref().push::<Bytes, _>(supported)?;
+    provider.as_ref().push::<Bytes, _>(code)?;
+    registry
+        .register_verified(provider.clone(), address, &verification)
+        .await?;
+    assert!(registry.contains(address));
+
+    Ok(())
+}